@@ -0,0 +1,212 @@
+//! Timing of the supported DShot variants.
+//!
+//! The number in the name of each variant is its bitrate in kbit/s. Every bit takes the same
+//! amount of time on the wire, so the PWM timer needs to run at the bitrate for each duty cycle
+//! to cover exactly one bit. Within that period, ones are high for twice as long as zeroes.
+
+/// The speed at which frames are transmitted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bitrate {
+    Dshot150,
+    Dshot300,
+    Dshot600,
+    Dshot1200,
+}
+
+impl Bitrate {
+    /// Returns the frequency in Hz the PWM timer needs to run at, which is the number of bits
+    /// transmitted per second.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Bitrate::Dshot600.timer_hz(), 600_000);
+    /// ```
+    #[must_use]
+    pub const fn timer_hz(&self) -> u32 {
+        match self {
+            Self::Dshot150 => 150_000,
+            Self::Dshot300 => 300_000,
+            Self::Dshot600 => 600_000,
+            Self::Dshot1200 => 1_200_000,
+        }
+    }
+
+    /// Returns the duration of a single bit in nanoseconds, rounded to the nearest nanosecond.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Bitrate::Dshot600.bit_period_ns(), 1667);
+    /// ```
+    #[must_use]
+    pub const fn bit_period_ns(&self) -> u32 {
+        let hz = self.timer_hz();
+        (1_000_000_000 + hz / 2) / hz
+    }
+}
+
+/// The duty cycle that holds the line low between frames.
+pub const RESET_PULSE: u16 = 0;
+
+/// The fraction of a bit period the line is high for ones and zeroes.
+///
+/// The denominators must not be zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitTiming {
+    /// The numerator of the fraction for ones.
+    pub one_numerator: u16,
+    /// The denominator of the fraction for ones.
+    pub one_denominator: u16,
+    /// The numerator of the fraction for zeroes.
+    pub zero_numerator: u16,
+    /// The denominator of the fraction for zeroes.
+    pub zero_denominator: u16,
+}
+
+impl BitTiming {
+    /// The timing used by [`Frame::duty_cycles`](crate::Frame::duty_cycles), 3/4 for ones and
+    /// 3/8 for zeroes.
+    pub const DEFAULT: Self = Self {
+        one_numerator: 3,
+        one_denominator: 4,
+        zero_numerator: 3,
+        zero_denominator: 8,
+    };
+
+    /// The timing as specified, 74.85% for ones and 37.425% for zeroes.
+    pub const SPEC: Self = Self {
+        one_numerator: 1497,
+        one_denominator: 2000,
+        zero_numerator: 1497,
+        zero_denominator: 4000,
+    };
+
+    /// Returns the duty cycle of a one, rounded to the nearest integer, with ties rounded down.
+    #[must_use]
+    pub const fn one_duty_cycle(&self, max_duty_cycle: u16) -> u16 {
+        scale(max_duty_cycle, self.one_numerator, self.one_denominator)
+    }
+
+    /// Returns the duty cycle of a zero, rounded to the nearest integer, with ties rounded down.
+    #[must_use]
+    pub const fn zero_duty_cycle(&self, max_duty_cycle: u16) -> u16 {
+        scale(max_duty_cycle, self.zero_numerator, self.zero_denominator)
+    }
+
+    /// Returns the duty cycle halfway between a zero and a one, rounded down.
+    ///
+    /// Duty cycles above this are ones, everything else is a zero.
+    #[must_use]
+    pub const fn threshold(&self, max_duty_cycle: u16) -> u16 {
+        ((self.zero_duty_cycle(max_duty_cycle) as u32 + self.one_duty_cycle(max_duty_cycle) as u32)
+            / 2) as u16
+    }
+
+    /// Returns how long a one is high at the given bitrate in nanoseconds, rounded to the nearest
+    /// nanosecond.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(BitTiming::DEFAULT.one_high_ns(Bitrate::Dshot150), 5000);
+    /// ```
+    #[must_use]
+    pub const fn one_high_ns(&self, bitrate: Bitrate) -> u32 {
+        high_ns(bitrate, self.one_numerator, self.one_denominator)
+    }
+
+    /// Returns how long a zero is high at the given bitrate in nanoseconds, rounded to the nearest
+    /// nanosecond.
+    #[must_use]
+    pub const fn zero_high_ns(&self, bitrate: Bitrate) -> u32 {
+        high_ns(bitrate, self.zero_numerator, self.zero_denominator)
+    }
+}
+
+impl Default for BitTiming {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Returns the duty cycle used by [`Frame::from_duty_cycles`](crate::Frame::from_duty_cycles) to
+/// tell zeroes and ones apart, see [`BitTiming::threshold`].
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(bit_threshold(100), 56);
+/// assert!(BitTiming::DEFAULT.one_duty_cycle(100) > bit_threshold(100));
+/// assert!(BitTiming::DEFAULT.zero_duty_cycle(100) <= bit_threshold(100));
+/// ```
+#[must_use]
+pub const fn bit_threshold(max_duty_cycle: u16) -> u16 {
+    BitTiming::DEFAULT.threshold(max_duty_cycle)
+}
+
+/// Scales the value by the given fraction, rounding to the nearest integer and without
+/// overflowing on the way.
+///
+/// Ties are rounded down, as the spec ratios are slightly below the 3/4 and 3/8 defaults.
+const fn scale(value: u16, numerator: u16, denominator: u16) -> u16 {
+    let denominator = denominator as u32;
+    ((value as u32 * numerator as u32 + (denominator - 1) / 2) / denominator) as u16
+}
+
+/// Returns the given fraction of a bit period in nanoseconds, rounded to the nearest nanosecond.
+const fn high_ns(bitrate: Bitrate, numerator: u16, denominator: u16) -> u32 {
+    let divisor = denominator as u64 * bitrate.timer_hz() as u64;
+    ((1_000_000_000 * numerator as u64 + divisor / 2) / divisor) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_periods_are_rounded() {
+        assert_eq!(Bitrate::Dshot150.bit_period_ns(), 6667);
+        assert_eq!(Bitrate::Dshot300.bit_period_ns(), 3333);
+        assert_eq!(Bitrate::Dshot600.bit_period_ns(), 1667);
+        assert_eq!(Bitrate::Dshot1200.bit_period_ns(), 833);
+    }
+
+    #[test]
+    fn spec_timing_is_more_precise() {
+        assert_eq!(BitTiming::DEFAULT.one_duty_cycle(4000), 3000);
+        assert_eq!(BitTiming::SPEC.one_duty_cycle(4000), 2994);
+        assert_eq!(BitTiming::SPEC.zero_duty_cycle(4000), 1497);
+    }
+
+    #[test]
+    fn high_times_in_nanoseconds() {
+        assert_eq!(BitTiming::DEFAULT.one_high_ns(Bitrate::Dshot600), 1250);
+        assert_eq!(BitTiming::DEFAULT.zero_high_ns(Bitrate::Dshot600), 625);
+        assert_eq!(BitTiming::SPEC.one_high_ns(Bitrate::Dshot150), 4990);
+        assert_eq!(BitTiming::SPEC.zero_high_ns(Bitrate::Dshot1200), 312);
+    }
+
+    #[test]
+    fn threshold_separates_bits_at_full_range() {
+        let timing = BitTiming::DEFAULT;
+        assert_eq!(timing.threshold(u16::MAX), 36863);
+        assert!(timing.zero_duty_cycle(u16::MAX) < timing.threshold(u16::MAX));
+    }
+
+    #[test]
+    fn duty_cycles_round_ties_towards_spec() {
+        // 3/8 of 100 is 37.5, 3/4 of 100 is exactly 75.
+        assert_eq!(BitTiming::DEFAULT.zero_duty_cycle(100), 37);
+        assert_eq!(BitTiming::DEFAULT.one_duty_cycle(100), 75);
+        // 3/8 of 20 is 7.5, 3/4 of 20 is exactly 15.
+        assert_eq!(BitTiming::DEFAULT.zero_duty_cycle(20), 7);
+        assert_eq!(BitTiming::DEFAULT.one_duty_cycle(20), 15);
+        // 3/8 of 10 is 3.75, 3/4 of 10 is 7.5.
+        assert_eq!(BitTiming::DEFAULT.zero_duty_cycle(10), 4);
+        assert_eq!(BitTiming::DEFAULT.one_duty_cycle(10), 7);
+        // 74.85% of 100 is 74.85, 37.425% of 100 is 37.425.
+        assert_eq!(BitTiming::SPEC.one_duty_cycle(100), 75);
+        assert_eq!(BitTiming::SPEC.zero_duty_cycle(100), 37);
+    }
+}
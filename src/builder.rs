@@ -0,0 +1,115 @@
+//! A builder for frames with optional settings.
+
+use crate::{Command, Frame, FrameError, Payload};
+
+/// A builder for [`Frame`]s, as an alternative to the constructors on [`Frame`].
+///
+/// Without setting a speed or command, this builds a motor stop frame.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = FrameBuilder::new().speed(1000).telemetry(true).build();
+/// assert_eq!(frame, Ok(Frame::new(1000, true).unwrap()));
+/// let frame = FrameBuilder::new().command(Command::Beep1).bidirectional(true).build();
+/// assert_eq!(frame, Ok(Frame::command_bidirectional(Command::Beep1, false)));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameBuilder {
+    payload: Payload,
+    request_telemetry: bool,
+    bidirectional: bool,
+}
+
+impl FrameBuilder {
+    /// Creates a new builder for a motor stop frame without telemetry.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            payload: Payload::Command(Command::MotorStop),
+            request_telemetry: false,
+            bidirectional: false,
+        }
+    }
+
+    /// Sets the speed (0-1999), replacing any command.
+    #[must_use]
+    pub const fn speed(mut self, speed: u16) -> Self {
+        self.payload = Payload::Throttle(speed);
+        self
+    }
+
+    /// Sets the [`Command`], replacing any speed.
+    #[must_use]
+    pub const fn command(mut self, command: Command) -> Self {
+        self.payload = Payload::Command(command);
+        self
+    }
+
+    /// Sets whether to request telemetry.
+    #[must_use]
+    pub const fn telemetry(mut self, request_telemetry: bool) -> Self {
+        self.request_telemetry = request_telemetry;
+        self
+    }
+
+    /// Sets whether to build a bidirectional frame, see [`Frame::new_bidirectional`].
+    #[must_use]
+    pub const fn bidirectional(mut self, bidirectional: bool) -> Self {
+        self.bidirectional = bidirectional;
+        self
+    }
+
+    /// Builds the frame.
+    ///
+    /// Returns [`FrameError::SpeedOutOfRange`] if the speed is out of bounds.
+    pub const fn build(&self) -> Result<Frame, FrameError> {
+        match self.payload {
+            Payload::Throttle(speed) => {
+                let frame = if self.bidirectional {
+                    Frame::new_bidirectional(speed, self.request_telemetry)
+                } else {
+                    Frame::new(speed, self.request_telemetry)
+                };
+                match frame {
+                    Some(frame) => Ok(frame),
+                    None => Err(FrameError::SpeedOutOfRange(speed)),
+                }
+            }
+            Payload::Command(command) if self.bidirectional => Ok(Frame::command_bidirectional(
+                command,
+                self.request_telemetry,
+            )),
+            Payload::Command(command) => Ok(Frame::command(command, self.request_telemetry)),
+            Payload::Reserved(value) => Err(FrameError::InvalidCommand(value)),
+        }
+    }
+}
+
+impl Default for FrameBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn builder_matches_constructors() {
+        assert_eq!(FrameBuilder::new().build(), Ok(Frame::default()));
+        assert_eq!(
+            FrameBuilder::new()
+                .speed(998)
+                .telemetry(true)
+                .bidirectional(true)
+                .build(),
+            Ok(Frame::new_bidirectional(998, true).unwrap())
+        );
+        assert_eq!(
+            FrameBuilder::new().speed(2000).build(),
+            Err(FrameError::SpeedOutOfRange(2000))
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! The checksum shared by frames and telemetry.
+
+/// Computes the four bit checksum over the given 12 bits of data.
+///
+/// This is the XOR of the three nibbles, as used by [`Frame`](crate::Frame). Bits above the
+/// lower 12 are ignored.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = Frame::new(1000, true).unwrap();
+/// assert_eq!(dshot_crc(frame.inner() >> 4), frame.crc());
+/// ```
+#[must_use]
+pub const fn dshot_crc(value: u16) -> u16 {
+    let value = value & 0x0FFF;
+    (value ^ (value >> 4) ^ (value >> 8)) & 0x0F
+}
+
+/// Computes the inverted four bit checksum over the given 12 bits of data, as used by
+/// bidirectional DShot.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = Frame::new_bidirectional(1000, true).unwrap();
+/// assert_eq!(dshot_crc_inverted(frame.inner() >> 4), frame.crc());
+/// ```
+#[must_use]
+pub const fn dshot_crc_inverted(value: u16) -> u16 {
+    !dshot_crc(value) & 0x0F
+}
+
+/// The checksum variants used by frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrcVariant {
+    /// The checksum of regular frames, see [`dshot_crc`].
+    Normal,
+    /// The inverted checksum of bidirectional frames, see [`dshot_crc_inverted`].
+    Inverted,
+}
+
+/// Computes the four bit checksum of a frame, for use with [`Frame::new_with_checksum`].
+///
+/// [`CrcVariant::standard`] is the standard checksum and [`CrcVariant::bidirectional`] the
+/// bidirectional one. Closures taking the 12 bits of data and returning the checksum can be used
+/// to test compatibility with nonstandard implementations.
+///
+/// Only the computation can be changed, the checksum is always stored in the last four bits of
+/// the frame.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = Frame::new_with_checksum(1000, false, CrcVariant::bidirectional());
+/// assert_eq!(frame, Frame::new_bidirectional(1000, false));
+/// ```
+///
+/// [`Frame::new_with_checksum`]: crate::Frame::new_with_checksum
+pub trait ChecksumStrategy {
+    /// Computes the checksum over the given 12 bits of data.
+    ///
+    /// Only the lower four bits of the result are used.
+    fn checksum(&self, data: u16) -> u16;
+}
+
+impl CrcVariant {
+    /// Returns the standard checksum strategy, [`CrcVariant::Normal`].
+    #[must_use]
+    pub const fn standard() -> Self {
+        Self::Normal
+    }
+
+    /// Returns the checksum strategy of bidirectional frames, [`CrcVariant::Inverted`].
+    #[must_use]
+    pub const fn bidirectional() -> Self {
+        Self::Inverted
+    }
+}
+
+impl ChecksumStrategy for CrcVariant {
+    fn checksum(&self, data: u16) -> u16 {
+        match self {
+            Self::Normal => dshot_crc(data),
+            Self::Inverted => dshot_crc_inverted(data),
+        }
+    }
+}
+
+impl<F: Fn(u16) -> u16> ChecksumStrategy for F {
+    fn checksum(&self, data: u16) -> u16 {
+        self(data)
+    }
+}
+
+/// Returns which checksum variant a raw frame uses, or [`None`] if its checksum is invalid.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = Frame::new_bidirectional(1000, false).unwrap();
+/// assert_eq!(detect_crc_variant(frame.inner()), Some(CrcVariant::Inverted));
+/// assert_eq!(detect_crc_variant(frame.inner() ^ 0x01), None);
+/// ```
+#[must_use]
+pub const fn detect_crc_variant(value: u16) -> Option<CrcVariant> {
+    let data = value >> 4;
+    let crc = value & 0x0F;
+    if crc == dshot_crc(data) {
+        Some(CrcVariant::Normal)
+    } else if crc == dshot_crc_inverted(data) {
+        Some(CrcVariant::Inverted)
+    } else {
+        None
+    }
+}
+
+/// Computes the checksum of [`dshot_crc`] incrementally, as the data arrives.
+///
+/// Data is fed MSB first, either a bit or a nibble at a time. Nibbles are placed at the current
+/// bit position, so both can be mixed.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = Frame::new(1000, false).unwrap();
+/// let mut crc = CrcAccumulator::new();
+/// for shift in (4..16).rev() {
+///     crc.push_bit((frame.inner() >> shift) & 1 != 0);
+/// }
+/// assert_eq!(crc.crc(), frame.crc());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CrcAccumulator {
+    crc: u16,
+    bits: u8,
+}
+
+impl CrcAccumulator {
+    /// Creates a new accumulator without any data.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { crc: 0, bits: 0 }
+    }
+
+    /// Feeds a single bit.
+    pub const fn push_bit(&mut self, bit: bool) {
+        if bit {
+            self.crc ^= 0x08 >> (self.bits % 4);
+        }
+        self.bits = self.bits.wrapping_add(1);
+    }
+
+    /// Feeds the lower four bits of the given value.
+    pub const fn push_nibble(&mut self, nibble: u8) {
+        let mut shift = 4;
+        while shift > 0 {
+            shift -= 1;
+            self.push_bit((nibble >> shift) & 1 != 0);
+        }
+    }
+
+    /// Returns the number of bits fed so far.
+    #[must_use]
+    pub const fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns the checksum of the data fed so far.
+    #[must_use]
+    pub const fn crc(&self) -> u16 {
+        self.crc
+    }
+
+    /// Returns the inverted checksum of the data fed so far, as used by bidirectional DShot.
+    #[must_use]
+    pub const fn crc_inverted(&self) -> u16 {
+        !self.crc & 0x0F
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dshot_crc_ignores_upper_bits() {
+        assert_eq!(dshot_crc(0xF123), dshot_crc(0x0123));
+        assert_eq!(dshot_crc(0x0123), 0x01 ^ 0x02 ^ 0x03);
+    }
+
+    #[test]
+    fn dshot_crc_inverted_inverts_all_four_bits() {
+        for value in 0..0x1000 {
+            assert_eq!(dshot_crc(value) ^ dshot_crc_inverted(value), 0x0F);
+        }
+    }
+
+    #[test]
+    fn detect_crc_variant_works() {
+        let data = 0x0123;
+        assert_eq!(
+            detect_crc_variant(data << 4 | dshot_crc(data)),
+            Some(CrcVariant::Normal)
+        );
+        assert_eq!(
+            detect_crc_variant(data << 4 | dshot_crc_inverted(data)),
+            Some(CrcVariant::Inverted)
+        );
+        assert_eq!(
+            detect_crc_variant(data << 4 | (dshot_crc(data) ^ 0x01)),
+            None
+        );
+    }
+
+    #[test]
+    fn crc_accumulator_matches_dshot_crc() {
+        for value in 0..0x1000u16 {
+            let mut crc = CrcAccumulator::new();
+            crc.push_nibble((value >> 8) as u8);
+            crc.push_bit(value & 0x80 != 0);
+            crc.push_bit(value & 0x40 != 0);
+            crc.push_bit(value & 0x20 != 0);
+            crc.push_bit(value & 0x10 != 0);
+            crc.push_nibble(value as u8 & 0x0F);
+            assert_eq!(crc.bits(), 12);
+            assert_eq!(crc.crc(), dshot_crc(value));
+            assert_eq!(crc.crc_inverted(), dshot_crc_inverted(value));
+        }
+    }
+}
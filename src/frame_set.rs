@@ -0,0 +1,99 @@
+//! A fixed number of frames, one per motor.
+
+use crate::{interleave_frames, BufferError, BufferLayout, Frame, DUTY_CYCLES_LEN};
+
+/// A set of `N` frames, one per motor channel.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let mut frames = FrameSet::<4>::default();
+/// *frames.get_mut(2).unwrap() = Frame::new(1000, false).unwrap();
+/// let duty_cycles = frames.duty_cycles(100);
+/// assert_eq!(duty_cycles[2], Frame::new(1000, false).unwrap().duty_cycles(100));
+/// assert_eq!(duty_cycles[0], Frame::motor_stop().duty_cycles(100));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameSet<const N: usize> {
+    frames: [Frame; N],
+}
+
+impl<const N: usize> FrameSet<N> {
+    /// Creates a new set from one frame per channel.
+    #[must_use]
+    pub const fn new(frames: [Frame; N]) -> Self {
+        Self { frames }
+    }
+
+    /// Returns the frames of all channels.
+    #[must_use]
+    pub const fn frames(&self) -> &[Frame; N] {
+        &self.frames
+    }
+
+    /// Returns the frame of the given channel, or [`None`] if it is out of bounds.
+    #[must_use]
+    pub const fn get(&self, channel: usize) -> Option<Frame> {
+        if channel < N {
+            Some(self.frames[channel])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the frame of the given channel, or [`None`] if it is out of
+    /// bounds.
+    pub fn get_mut(&mut self, channel: usize) -> Option<&mut Frame> {
+        self.frames.get_mut(channel)
+    }
+
+    /// Returns the duty cycles of every channel, see [`Frame::duty_cycles`].
+    #[must_use]
+    pub fn duty_cycles(&self, max_duty_cycle: u16) -> [[u16; DUTY_CYCLES_LEN]; N] {
+        self.frames.map(|frame| frame.duty_cycles(max_duty_cycle))
+    }
+
+    /// Writes the duty cycles of every channel into the given buffer in the given layout, see
+    /// [`interleave_frames`].
+    pub fn interleave(
+        &self,
+        max_duty_cycle: u16,
+        layout: BufferLayout,
+        out: &mut [u16],
+    ) -> Result<usize, BufferError> {
+        interleave_frames(&self.frames, max_duty_cycle, layout, out)
+    }
+}
+
+impl<const N: usize> Default for FrameSet<N> {
+    /// Returns a set of [`Frame::motor_stop`] frames.
+    fn default() -> Self {
+        Self::new([Frame::motor_stop(); N])
+    }
+}
+
+impl<const N: usize> From<[Frame; N]> for FrameSet<N> {
+    fn from(frames: [Frame; N]) -> Self {
+        Self::new(frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn interleave_matches_free_function() {
+        let frames = [Frame::new(998, false).unwrap(), Frame::idle()];
+        let mut expected = [0; 34];
+        interleave_frames(&frames, 100, BufferLayout::BitMajor, &mut expected).unwrap();
+        let mut buffer = [0; 34];
+        let set = FrameSet::from(frames);
+        assert_eq!(
+            set.interleave(100, BufferLayout::BitMajor, &mut buffer),
+            Ok(34)
+        );
+        assert_eq!(buffer, expected);
+        assert_eq!(set.get(2), None);
+    }
+}
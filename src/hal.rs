@@ -0,0 +1,67 @@
+//! Integration with `embedded-hal` PWM channels.
+
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::Frame;
+
+/// Sends a frame over a PWM channel, one duty cycle per PWM period, and pulls the line low
+/// afterwards.
+///
+/// The PWM timer needs to run at the frame's bitrate, see
+/// [`Bitrate::timer_hz`](crate::Bitrate::timer_hz). After setting each duty cycle,
+/// `wait_for_period` is called and needs to block until the next PWM period begins, for example by
+/// polling the timer's update flag.
+pub fn send_frame<P: SetDutyCycle>(
+    pwm: &mut P,
+    frame: &Frame,
+    mut wait_for_period: impl FnMut(),
+) -> Result<(), P::Error> {
+    for duty_cycle in frame.duty_cycles(pwm.max_duty_cycle()) {
+        pwm.set_duty_cycle(duty_cycle)?;
+        wait_for_period();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::convert::Infallible;
+    use std::vec::Vec;
+
+    use embedded_hal::pwm::ErrorType;
+
+    use super::*;
+
+    struct RecordingPwm {
+        duty_cycles: Vec<u16>,
+    }
+
+    impl ErrorType for RecordingPwm {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for RecordingPwm {
+        fn max_duty_cycle(&self) -> u16 {
+            100
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            self.duty_cycles.push(duty);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn send_frame_ends_low() {
+        let mut pwm = RecordingPwm {
+            duty_cycles: Vec::new(),
+        };
+        let mut periods = 0;
+        let frame = Frame::new(1000, false).unwrap();
+        send_frame(&mut pwm, &frame, || periods += 1).unwrap();
+        assert_eq!(pwm.duty_cycles, frame.duty_cycles(100));
+        assert_eq!(periods, 17);
+    }
+}
@@ -3,7 +3,9 @@
 //! DShot has two-byte frames, where the first 11 bits are the throttle speed, bit 12 is a
 //! telemetry request flag, and the last four bits are a checksum.
 //!
-//! Throttle values below 48 are reserved for special commands.
+//! Throttle values below 48 are reserved for special commands. The remaining values 48-2047 are
+//! the actual throttle, which this crate exposes as speeds 0-1999, so the full 11 bit range is
+//! covered.
 //!
 //! It is transmitted over the wire at a fixed speed, with ones and zeroes both being pulses, but
 //! ones being twice as long as zeroes.
@@ -19,7 +21,7 @@
 //!     None,
 //!     None,
 //!     None,
-//!     Hertz(150_000),
+//!     Hertz(Bitrate::Dshot150.timer_hz()),
 //!     CountingMode::EdgeAlignedUp,
 //! );
 //! let max_duty_cycle = pwm.get_max_duty() as u16;
@@ -30,29 +32,150 @@
 //! pwm.set_duty(channel, 0);
 //! pwm.enable(channel);
 //! ```
-
-// TODO Bidirectional DShot
+//!
+//! ## Bidirectional DShot
+//!
+//! Bidirectional frames are built with [`Frame::new_bidirectional`] and
+//! [`Frame::command_bidirectional`], and the eRPM telemetry the ESC sends back can be decoded with
+//! [`decode_erpm`].
+//!
+//! ## Features
+//!
+//! - `defmt`: Implements [`defmt::Format`](https://docs.rs/defmt) for all public types.
+//! - `embedded-hal`: Enables `send_frame`, which sends a frame over any
+//!   [`SetDutyCycle`](https://docs.rs/embedded-hal) PWM channel.
+//! - `float`: Enables floating point APIs, which can be costly on MCUs without an FPU:
+//!   `Frame::duty_fractions`, for hardware and simulations that take duty cycles as fractions,
+//!   and `throttle_from_fraction`, which converts a throttle fraction into a speed. Use
+//!   [`throttle_from_permille`] to do the same without floating point math.
+//! - `heapless`: Enables `Frame::duty_cycles_vec`, which returns a
+//!   [`heapless::Vec`](https://docs.rs/heapless) that can be extended with more frames or gaps.
+//! - `serde`: Implements [`Serialize`](https://docs.rs/serde) and
+//!   [`Deserialize`](https://docs.rs/serde) for frames, commands and telemetry. Frames are
+//!   (de)serialized as their raw [`u16`], and deserializing one fails if its checksum is neither
+//!   the normal nor the bidirectional one.
+//! - `ufmt`: Implements [`uDebug`](https://docs.rs/ufmt) for frames and commands, and
+//!   [`uDisplay`](https://docs.rs/ufmt) for frames, for logging without `core::fmt`.
 
 #![no_std]
 
+use core::fmt;
+
+mod bitrate;
+mod builder;
+mod crc;
+mod frame_set;
+#[cfg(feature = "embedded-hal")]
+mod hal;
+mod sequence;
+mod telemetry;
+#[cfg(feature = "ufmt")]
+mod ufmt_impls;
+
+pub use bitrate::*;
+pub use builder::*;
+pub use crc::*;
+pub use frame_set::*;
+#[cfg(feature = "embedded-hal")]
+pub use hal::*;
+pub use sequence::*;
+pub use telemetry::*;
+
+/// The number of bits in a frame, for array lengths and indexing.
+///
+/// [`Frame::BITS`] is the same value as a [`u32`].
+pub const FRAME_BITS: usize = 16;
+
+/// The number of duty cycles returned by [`Frame::duty_cycles`], one per bit plus a trailing
+/// [`RESET_PULSE`].
+pub const DUTY_CYCLES_LEN: usize = FRAME_BITS + 1;
+
 /// A frame of two bytes that get send over the wire.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Frame {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_checked"))]
     inner: u16,
 }
 
+/// Deserializes a raw frame, rejecting it unless it has a normal or bidirectional checksum.
+#[cfg(feature = "serde")]
+fn deserialize_checked<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u16, D::Error> {
+    let value = <u16 as serde::Deserialize>::deserialize(deserializer)?;
+    if detect_crc_variant(value).is_none() {
+        return Err(serde::de::Error::custom(CrcError {
+            expected: dshot_crc(value >> 4),
+            actual: value & 0x0F,
+        }));
+    }
+    Ok(value)
+}
+
 impl Frame {
+    /// The number of bits in a frame, [`FRAME_BITS`] as a [`u32`] for timing calculations.
+    ///
+    /// Use [`FRAME_BITS`] for array lengths and indexing.
+    pub const BITS: u32 = FRAME_BITS as u32;
+
+    /// Returns how long it takes to transmit a frame at the given bitrate in nanoseconds, rounded
+    /// to the nearest nanosecond.
+    ///
+    /// This does not include any time the line is held low after the frame.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::wire_duration_ns(Bitrate::Dshot600), 26_667);
+    /// ```
+    #[must_use]
+    pub const fn wire_duration_ns(bitrate: Bitrate) -> u32 {
+        let hz = bitrate.timer_hz() as u64;
+        ((Self::BITS as u64 * 1_000_000_000 + hz / 2) / hz) as u32
+    }
+
     /// Creates a new frame with the given speed (0-1999) and telemetry request.
     ///
-    /// Returns [`None`] if the speed is out of bounds.
+    /// Returns [`None`] if the speed is out of bounds. This is a thin wrapper around
+    /// [`Frame::try_new`], which reports why a frame could not be created.
+    ///
+    /// Like the other constructors, this can be used in a `const` context to build frame tables at
+    /// compile time.
     ///
     /// ```
     /// # use dshot_frame::*;
     /// assert_eq!(Frame::new(1000, false).unwrap().speed(), 1000);
+    ///
+    /// const STEPS: [Option<Frame>; 2] = [Frame::new(0, false), Frame::new(1000, false)];
+    /// assert_eq!(STEPS[1].unwrap().speed(), 1000);
+    /// ```
+    #[must_use]
+    pub const fn new(speed: u16, request_telemetry: bool) -> Option<Self> {
+        match Self::try_new(speed, request_telemetry) {
+            Ok(frame) => Some(frame),
+            Err(_) => None,
+        }
+    }
+
+    /// Creates a new frame with the given speed (0-1999) and telemetry request.
+    ///
+    /// The speed is offset by the 48 command values, so the maximum speed of 1999 is sent as
+    /// 2047, the top of the 11 bit range.
+    ///
+    /// Returns [`FrameError::SpeedOutOfRange`] if the speed is out of bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::try_new(1000, false).unwrap().speed(), 1000);
+    /// assert_eq!(
+    ///     Frame::try_new(2000, false).unwrap_err(),
+    ///     FrameError::SpeedOutOfRange(2000)
+    /// );
     /// ```
-    pub fn new(speed: u16, request_telemetry: bool) -> Option<Self> {
+    pub const fn try_new(speed: u16, request_telemetry: bool) -> Result<Self, FrameError> {
         if speed >= 2000 {
-            return None;
+            return Err(FrameError::SpeedOutOfRange(speed));
         }
 
         let translated_throttle = (speed + 48) << 5;
@@ -63,11 +186,111 @@ impl Frame {
             frame.inner |= 0x10;
         }
         frame.compute_crc();
-        Some(frame)
+        Ok(frame)
+    }
+
+    /// Creates a new frame for an ESC in 3D mode, with the given direction, magnitude (0-999) and
+    /// telemetry request.
+    ///
+    /// In 3D mode the throttle range is split in half, the lower half (48-1047) spinning the motor
+    /// in reverse and the upper half (1048-2047) forward, as Betaflight and AM32 do.
+    ///
+    /// Returns [`None`] if the magnitude is out of bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new_3d(Direction3D::Forward, 500, false).unwrap();
+    /// assert_eq!(frame.speed(), 1500);
+    /// assert!(Frame::new_3d(Direction3D::Forward, 1000, false).is_none());
+    /// ```
+    #[must_use]
+    pub const fn new_3d(
+        direction: Direction3D,
+        magnitude: u16,
+        request_telemetry: bool,
+    ) -> Option<Self> {
+        if magnitude >= 1000 {
+            return None;
+        }
+        let speed = match direction {
+            Direction3D::Forward => magnitude + 1000,
+            Direction3D::Reverse => magnitude,
+        };
+        Self::new(speed, request_telemetry)
+    }
+
+    /// Creates a new frame for an ESC in 3D mode from a signed throttle (-1000-1000) and telemetry
+    /// request.
+    ///
+    /// Positive values spin the motor forward in the upper half of the throttle range (1048-2047),
+    /// negative values in reverse in the lower half (48-1047), see [`Frame::new_3d`]. The
+    /// magnitude is scaled to 0-999, and zero is the slowest forward throttle.
+    ///
+    /// Returns [`None`] if the throttle is out of bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(
+    ///     Frame::new_3d_signed(-1000, false),
+    ///     Frame::new_3d(Direction3D::Reverse, 999, false)
+    /// );
+    /// assert_eq!(
+    ///     Frame::new_3d_signed(0, false),
+    ///     Frame::new_3d(Direction3D::Forward, 0, false)
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn new_3d_signed(throttle: i16, request_telemetry: bool) -> Option<Self> {
+        if throttle < -1000 || throttle > 1000 {
+            return None;
+        }
+        let direction = if throttle < 0 {
+            Direction3D::Reverse
+        } else {
+            Direction3D::Forward
+        };
+        let magnitude = (throttle.unsigned_abs() as u32 * 999 + 500) / 1000;
+        Self::new_3d(direction, magnitude as u16, request_telemetry)
+    }
+
+    /// Creates a new frame with the given speed and telemetry request, rejecting speeds above
+    /// `max`.
+    ///
+    /// Returns [`None`] if the speed is above `max` or out of bounds, so a `max` above 1999 has no
+    /// effect.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert!(Frame::new_with_max(1200, 1500, false).is_some());
+    /// assert!(Frame::new_with_max(1600, 1500, false).is_none());
+    /// ```
+    #[must_use]
+    pub const fn new_with_max(speed: u16, max: u16, request_telemetry: bool) -> Option<Self> {
+        if speed > max {
+            return None;
+        }
+        Self::new(speed, request_telemetry)
+    }
+
+    /// Creates a new frame with the given speed and telemetry request, clamping the speed to
+    /// 0-1999.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::new_clamped(2500, false).speed(), 1999);
+    /// ```
+    #[must_use]
+    pub const fn new_clamped(speed: u16, request_telemetry: bool) -> Self {
+        let speed = if speed > 1999 { 1999 } else { speed };
+        match Self::try_new(speed, request_telemetry) {
+            Ok(frame) => frame,
+            Err(_) => unreachable!(),
+        }
     }
 
     /// Creates a new frame with the given [`Command`] and telemetry request.
-    pub fn command(command: Command, request_telemetry: bool) -> Self {
+    #[must_use]
+    pub const fn command(command: Command, request_telemetry: bool) -> Self {
         let mut frame = Self {
             inner: (command as u16) << 5,
         };
@@ -78,167 +301,1895 @@ impl Frame {
         frame
     }
 
-    /// Returns the speed value (0-1999).
-    pub fn speed(&self) -> u16 {
-        (self.inner >> 5) - 48
+    /// Returns the frames needed for the ESC to act upon the given [`Command`], which is the same
+    /// frame repeated [`Command::required_repetitions`] times.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::command_burst(Command::SettingsSave, false).count(), 6);
+    /// ```
+    pub fn command_burst(
+        command: Command,
+        request_telemetry: bool,
+    ) -> impl Iterator<Item = Self> + Clone {
+        let frame = Self::command(command, request_telemetry);
+        core::iter::repeat_n(frame, command.required_repetitions() as usize)
     }
 
-    /// Returns whether telemetry is enabled.
-    pub fn telemetry_enabled(&self) -> bool {
-        self.inner & 0x10 != 0
+    /// Creates a new [`Command::MotorStop`] frame without telemetry request.
+    #[must_use]
+    pub const fn motor_stop() -> Self {
+        Self::command(Command::MotorStop, false)
     }
 
-    /// Returns the CRC checksum.
-    pub fn crc(&self) -> u16 {
-        self.inner & 0x0F
+    /// Creates a new frame with zero throttle without telemetry request.
+    ///
+    /// Unlike [`Frame::motor_stop`], this is a throttle frame, which keeps an armed ESC armed and
+    /// idle.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::idle().throttle_field(), 48);
+    /// ```
+    #[must_use]
+    pub const fn idle() -> Self {
+        Self::new_clamped(0, false)
     }
 
-    /// Computes the CRC based on the first 12 bits and ORs it in.
-    fn compute_crc(&mut self) {
-        let value = self.inner >> 4;
-        let crc = (value ^ (value >> 4) ^ (value >> 8)) & 0x0F;
-        self.inner |= crc;
+    /// Creates a new beep frame for the given tone (1-5) without telemetry request.
+    ///
+    /// Returns [`None`] if the tone is out of bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::beep(3), Some(Frame::command(Command::Beep3, false)));
+    /// assert_eq!(Frame::beep(6), None);
+    /// ```
+    #[must_use]
+    pub const fn beep(tone: u8) -> Option<Self> {
+        let command = match tone {
+            1 => Command::Beep1,
+            2 => Command::Beep2,
+            3 => Command::Beep3,
+            4 => Command::Beep4,
+            5 => Command::Beep5,
+            _ => return None,
+        };
+        Some(Self::command(command, false))
     }
 
-    /// Returns the raw [`u16`].
-    pub fn inner(&self) -> u16 {
-        self.inner
+    /// Creates a new [`Command::SettingsSave`] frame without telemetry request.
+    #[must_use]
+    pub const fn save_settings() -> Self {
+        Self::command(Command::SettingsSave, false)
     }
 
-    /// Returns an array of duty cycles for use in PWM DMA.
+    /// Creates a new bidirectional DShot frame with the given speed (0-1999) and telemetry
+    /// request.
     ///
-    /// This contains an extra element that is always zero to ensure the PWM output gets pulled low
-    /// at the end of the sequence. It can be sliced off if not needed.
-    pub fn duty_cycles(&self, max_duty_cycle: u16) -> [u16; 17] {
-        let mut value = self.inner;
-        let mut rv = [max_duty_cycle * 3 / 4; 17];
-        for item in rv.iter_mut() {
-            let bit = value & 0x8000;
-            if bit == 0 {
-                *item = max_duty_cycle * 3 / 8;
+    /// This is identical to [`Frame::new`], except that the checksum is inverted, which signals
+    /// the ESC to reply with eRPM telemetry.
+    ///
+    /// Returns [`None`] if the speed is out of bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let bidirectional = Frame::new_bidirectional(1000, false).unwrap();
+    /// assert_eq!(bidirectional.speed(), 1000);
+    /// assert_eq!(bidirectional.crc(), !frame.crc() & 0x0F);
+    /// ```
+    #[must_use]
+    pub const fn new_bidirectional(speed: u16, request_telemetry: bool) -> Option<Self> {
+        match Self::new(speed, request_telemetry) {
+            Some(mut frame) => {
+                frame.invert_crc();
+                Some(frame)
             }
-            value <<= 1;
+            None => None,
         }
-        rv[16] = 0;
-        rv
     }
-}
 
-/// Fixed commands that occupy the lower 48 speed values.
-///
-/// Some commands need to be sent multiple times to be acted upon to prevent accidental bit-flips
-/// wreaking havoc.
-#[derive(Copy, Clone, Debug)]
-pub enum Command {
-    MotorStop = 0,
-    /// Wait at least 260ms before next command.
-    Beep1,
-    /// Wait at least 260ms before next command.
-    Beep2,
-    /// Wait at least 260ms before next command.
-    Beep3,
-    /// Wait at least 260ms before next command.
-    Beep4,
-    /// Wait at least 260ms before next command.
-    Beep5,
-    /// Wait at least 12ms before next command.
-    ESCInfo,
-    /// Needs 6 transmissions.
-    SpinDirection1,
-    /// Needs 6 transmissions.
-    SpinDirection2,
-    /// Needs 6 transmissions.
-    ThreeDModeOn,
-    /// Needs 6 transmissions.
-    ThreeDModeOff,
-    SettingsRequest,
-    /// Needs 6 transmissions. Wait at least 35ms before next command.
-    SettingsSave,
-    /// Needs 6 transmissions.
-    ExtendedTelemetryEnable,
-    /// Needs 6 transmissions.
-    ExtendedTelemetryDisable,
+    /// Creates a new bidirectional DShot frame with the given [`Command`] and telemetry request.
+    ///
+    /// See [`Frame::new_bidirectional`].
+    #[must_use]
+    pub const fn command_bidirectional(command: Command, request_telemetry: bool) -> Self {
+        let mut frame = Self::command(command, request_telemetry);
+        frame.invert_crc();
+        frame
+    }
 
-    // 15-19 are unassigned.
-    /// Needs 6 transmissions.
-    SpinDirectionNormal = 20,
-    /// Needs 6 transmissions.
-    SpinDirectonReversed,
-    Led0On,
-    Led1On,
-    Led2On,
-    Led3On,
-    Led0Off,
-    Led1Off,
-    Led2Off,
-    Led3Off,
-    AudioStreamModeToggle,
-    SilentModeToggle,
-    /// Needs 6 transmissions. Enables individual signal line commands.
-    SignalLineTelemetryEnable,
-    /// Needs 6 transmissions. Disables individual signal line commands.
-    SignalLineTelemetryDisable,
-    /// Needs 6 transmissions. Enables individual signal line commands.
-    SignalLineContinuousERPMTelemetry,
-    /// Needs 6 transmissions. Enables individual signal line commands.
-    SignalLineContinuousERPMPeriodTelemetry,
+    /// Creates a new frame with the given speed (0-1999) and telemetry request, and the checksum
+    /// computed by the given [`ChecksumStrategy`], stored in the last four bits.
+    ///
+    /// Returns [`None`] if the speed is out of bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new_with_checksum(1000, false, CrcVariant::Inverted);
+    /// assert_eq!(frame, Frame::new_bidirectional(1000, false));
+    /// let frame = Frame::new_with_checksum(1000, false, |_| 0x0F).unwrap();
+    /// assert_eq!(frame.crc(), 0x0F);
+    /// ```
+    #[must_use]
+    pub fn new_with_checksum(
+        speed: u16,
+        request_telemetry: bool,
+        strategy: impl ChecksumStrategy,
+    ) -> Option<Self> {
+        let mut frame = Self::new(speed, request_telemetry)?;
+        frame.inner = (frame.inner & !0x0F) | (strategy.checksum(frame.inner >> 4) & 0x0F);
+        Some(frame)
+    }
 
-    // 36-41 are unassigned.
-    /// 1ºC per LSB.
-    SignalLineTemperatureTelemetry = 42,
-    /// 10mV per LSB, 40.95V max.
-    SignalLineVoltageTelemetry,
-    /// 100mA per LSB, 409.5A max.
-    SignalLineCurrentTelemetry,
-    /// 10mAh per LSB, 40.95Ah max.
-    SignalLineConsumptionTelemetry,
-    /// 100erpm per LSB, 409500erpm max.
-    SignalLineERPMTelemetry,
-    /// 16us per LSB, 65520us max.
-    SignalLineERPMPeriodTelemetry,
-}
+    /// Parses a raw [`u16`] into a frame, validating its checksum.
+    ///
+    /// This is the inverse of [`Frame::inner`], `Frame::from_raw(frame.inner())` returns the same
+    /// frame for every frame that is not bidirectional.
+    ///
+    /// Returns a [`CrcError`] if the stored checksum does not match the data.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(Frame::from_raw(frame.inner()).unwrap().speed(), 1000);
+    /// assert!(Frame::from_raw(frame.inner() ^ 0x01).is_err());
+    /// ```
+    pub fn from_raw(value: u16) -> Result<Self, CrcError> {
+        let mut frame = Self { inner: value };
+        frame.compute_crc();
+        let expected = frame.crc();
+        let actual = value & 0x0F;
+        if expected != actual {
+            return Err(CrcError { expected, actual });
+        }
+        Ok(frame)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Creates a frame from a raw [`u16`] as is, without validating its checksum.
+    ///
+    /// This is meant for testing how invalid frames are handled, use [`Frame::from_raw`]
+    /// otherwise.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::from_raw_unchecked(Frame::new(1000, false).unwrap().inner() ^ 0x01);
+    /// assert!(!frame.checksum_valid());
+    /// ```
+    #[must_use]
+    pub const fn from_raw_unchecked(value: u16) -> Self {
+        Self { inner: value }
+    }
 
-    const MAX_DUTY_CYCLE: u16 = 100;
-    const ZERO: u16 = 37;
-    const ONE: u16 = 75;
+    /// Returns the frame with the bit at the given index toggled, MSB first, without updating the
+    /// checksum.
+    ///
+    /// This is meant for testing how corrupted frames are handled. Any single flipped bit
+    /// invalidates the checksum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below [`FRAME_BITS`].
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap().with_bit_flipped(0);
+    /// assert!(!frame.checksum_valid());
+    /// assert_eq!(frame.with_bit_flipped(0), Frame::new(1000, false).unwrap());
+    /// ```
+    #[must_use]
+    pub const fn with_bit_flipped(self, index: u8) -> Self {
+        assert!((index as usize) < FRAME_BITS, "bit index out of range");
+        Self {
+            inner: self.inner ^ (0x8000 >> index),
+        }
+    }
 
-    #[test]
-    fn duty_cycles_works() {
-        let frame = Frame::new(999, false).unwrap();
-        assert_eq!(
-            frame.duty_cycles(MAX_DUTY_CYCLE),
-            [
-                ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ONE, ONE, ONE, ZERO, ZERO, ONE, ZERO,
-                ZERO, 0
-            ]
-        );
+    /// Decodes duty cycles, as returned by [`Frame::duty_cycles`], back into a frame, validating
+    /// its checksum.
+    ///
+    /// Each of the first 16 duty cycles is a one if it is above [`bit_threshold`], the midpoint
+    /// between the duty cycles of a zero and a one, which tolerates generous deviations from the
+    /// exact values. Any further elements, such as the trailing zero, are ignored.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(Frame::from_duty_cycles(&frame.duty_cycles(100), 100), Ok(frame));
+    /// ```
+    pub fn from_duty_cycles(cycles: &[u16], max_duty_cycle: u16) -> Result<Self, DecodeError> {
+        let Some(cycles) = cycles.get(..FRAME_BITS) else {
+            return Err(DecodeError::BufferTooShort(BufferError {
+                required: FRAME_BITS,
+                actual: cycles.len(),
+            }));
+        };
+        let threshold = bit_threshold(max_duty_cycle);
+        let value = cycles.iter().fold(0, |value, &duty_cycle| {
+            (value << 1) | (duty_cycle > threshold) as u16
+        });
+        Ok(Self::from_raw(value)?)
     }
 
-    #[test]
-    fn duty_cycles_at_zero() {
-        let frame = Frame::command(Command::MotorStop, false);
-        assert_eq!(
-            frame.duty_cycles(MAX_DUTY_CYCLE),
-            [
-                ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO,
-                ZERO, ZERO, 0
-            ]
-        );
+    /// Parses two bytes in transmission order into a frame, validating its checksum.
+    ///
+    /// This is the inverse of [`Frame::to_be_bytes`], see [`Frame::from_raw`].
+    pub fn from_be_bytes(bytes: [u8; 2]) -> Result<Self, CrcError> {
+        Self::from_raw(u16::from_be_bytes(bytes))
     }
 
-    #[test]
-    fn frame_constructs_correctly() {
-        let frame = Frame::new(998, false).unwrap();
-        assert_eq!(frame.speed(), 998);
-        assert!(!frame.telemetry_enabled());
-        assert_eq!(frame.crc(), 0x06);
+    /// Returns the frame as two bytes in transmission order, which is big-endian.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.to_be_bytes(), [0x83, 0x0B]);
+    /// assert_eq!(Frame::from_be_bytes(frame.to_be_bytes()), Ok(frame));
+    /// ```
+    #[must_use]
+    pub const fn to_be_bytes(&self) -> [u8; 2] {
+        self.inner.to_be_bytes()
     }
 
-    #[test]
+    /// Returns the speed value (0-1999).
+    ///
+    /// This is only meaningful for throttle frames, command frames return zero. Use
+    /// [`Frame::try_speed`] or [`Frame::payload`] if the frame might carry a command.
+    #[must_use]
+    pub const fn speed(&self) -> u16 {
+        self.throttle_field().saturating_sub(48)
+    }
+
+    /// Returns the speed value (0-1999), or [`None`] if the frame carries a command.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::new(1000, false).unwrap().try_speed(), Some(1000));
+    /// assert_eq!(Frame::command(Command::MotorStop, false).try_speed(), None);
+    /// ```
+    #[must_use]
+    pub const fn try_speed(&self) -> Option<u16> {
+        match self.payload() {
+            Payload::Throttle(speed) => Some(speed),
+            _ => None,
+        }
+    }
+
+    /// Returns the throttle as a percentage (0-100), rounded down, or [`None`] if the frame
+    /// carries a command.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::new(1000, false).unwrap().throttle_percent(), Some(50));
+    /// assert_eq!(Frame::new(1999, false).unwrap().throttle_percent(), Some(100));
+    /// assert_eq!(Frame::command(Command::MotorStop, false).throttle_percent(), None);
+    /// ```
+    #[must_use]
+    pub const fn throttle_percent(&self) -> Option<u8> {
+        match self.try_speed() {
+            Some(speed) => Some((speed as u32 * 100 / 1999) as u8),
+            None => None,
+        }
+    }
+
+    /// Returns the raw 11 bit throttle field as sent over the wire (0-2047).
+    ///
+    /// Unlike [`Frame::speed`], this is not offset by the 48 command values.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Frame::new(1000, false).unwrap().throttle_field(), 1048);
+    /// assert_eq!(Frame::command(Command::Beep2, false).throttle_field(), 2);
+    /// ```
+    #[must_use]
+    pub const fn throttle_field(&self) -> u16 {
+        self.inner >> 5
+    }
+
+    /// Returns whether both frames carry the same throttle value or command, ignoring the
+    /// telemetry request and checksum.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::command(Command::MotorStop, true);
+    /// assert!(frame.same_payload(&Frame::motor_stop()));
+    /// ```
+    #[must_use]
+    pub const fn same_payload(&self, other: &Self) -> bool {
+        self.throttle_field() == other.throttle_field()
+    }
+
+    /// Returns whether the frame carries a command rather than a throttle value.
+    ///
+    /// This includes values in the unassigned command ranges, see [`Command::is_reserved`].
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert!(Frame::command(Command::Beep1, false).is_command());
+    /// assert!(!Frame::new(0, false).unwrap().is_command());
+    /// ```
+    #[must_use]
+    pub const fn is_command(&self) -> bool {
+        self.throttle_field() < 48
+    }
+
+    /// Returns what the frame carries, either a throttle value or a command.
+    ///
+    /// Unlike [`Frame::speed`], this is correct for every frame, including ones parsed with
+    /// [`Frame::from_raw`].
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.payload(), Payload::Throttle(1000));
+    /// let frame = Frame::command(Command::Beep1, false);
+    /// assert_eq!(frame.payload(), Payload::Command(Command::Beep1));
+    /// ```
+    #[must_use]
+    pub const fn payload(&self) -> Payload {
+        let value = self.throttle_field();
+        if !self.is_command() {
+            return Payload::Throttle(value - 48);
+        }
+        match Command::from_u16(value) {
+            Some(command) => Payload::Command(command),
+            None => Payload::Reserved(value),
+        }
+    }
+
+    /// Returns whether telemetry is enabled.
+    #[must_use]
+    pub const fn telemetry_enabled(&self) -> bool {
+        self.inner & 0x10 != 0
+    }
+
+    /// Returns the same frame with the telemetry request set as given.
+    ///
+    /// The checksum is recomputed, keeping how it differs from the normal one: it stays inverted
+    /// for bidirectional frames, and corrupt checksums stay corrupt.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.with_telemetry(true), Frame::new(1000, true).unwrap());
+    /// ```
+    #[must_use]
+    pub const fn with_telemetry(mut self, request_telemetry: bool) -> Self {
+        let offset = self.crc_offset();
+        self.inner &= !0x1F;
+        if request_telemetry {
+            self.inner |= 0x10;
+        }
+        self.compute_crc();
+        self.inner ^= offset;
+        self
+    }
+
+    /// Sets the speed (0-1999) in place, keeping the telemetry request.
+    ///
+    /// The checksum is recomputed like in [`Frame::with_telemetry`], so it stays inverted for
+    /// bidirectional frames, and corrupt checksums stay corrupt. Returns
+    /// [`FrameError::SpeedOutOfRange`] and leaves the frame unchanged if the speed is out of
+    /// bounds.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let mut frame = Frame::new(1000, true).unwrap();
+    /// frame.set_speed(1500).unwrap();
+    /// assert_eq!(frame, Frame::new(1500, true).unwrap());
+    /// ```
+    pub const fn set_speed(&mut self, speed: u16) -> Result<(), FrameError> {
+        let mut frame = match Self::try_new(speed, self.telemetry_enabled()) {
+            Ok(frame) => frame,
+            Err(error) => return Err(error),
+        };
+        frame.inner ^= self.crc_offset();
+        *self = frame;
+        Ok(())
+    }
+
+    /// Returns the bits in which the stored checksum differs from the normal one, which is `0x0F`
+    /// for bidirectional frames and zero for regular ones.
+    const fn crc_offset(&self) -> u16 {
+        self.crc() ^ self.expected_crc()
+    }
+
+    /// Returns whether the stored checksum matches the data, either as the normal checksum or as
+    /// the inverted one of bidirectional frames.
+    ///
+    /// Use [`detect_crc_variant`] to tell which one it is.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert!(Frame::new(1000, false).unwrap().checksum_valid());
+    /// assert!(Frame::new_bidirectional(1000, false).unwrap().checksum_valid());
+    /// ```
+    #[must_use]
+    pub const fn checksum_valid(&self) -> bool {
+        detect_crc_variant(self.inner).is_some()
+    }
+
+    /// Returns the CRC checksum.
+    #[must_use]
+    pub const fn crc(&self) -> u16 {
+        self.inner & 0x0F
+    }
+
+    /// Returns the CRC checksum computed from the first 12 bits, including the telemetry bit,
+    /// regardless of the stored one.
+    ///
+    /// If this differs from [`Frame::crc`], the frame is corrupt. Bidirectional frames store the
+    /// inverse of this instead.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.expected_crc(), frame.crc());
+    /// let frame = frame.with_bit_flipped(0);
+    /// assert_ne!(frame.expected_crc(), frame.crc());
+    /// ```
+    #[must_use]
+    pub const fn expected_crc(&self) -> u16 {
+        dshot_crc(self.inner >> 4)
+    }
+
+    /// Computes the CRC based on the first 12 bits and replaces the last four bits with it.
+    ///
+    /// The first 12 bits include the telemetry bit, so toggling telemetry changes the CRC. Every
+    /// constructor and mutator that changes the throttle field or the telemetry bit needs to call
+    /// this afterwards. Constructors of bidirectional frames then call [`Frame::invert_crc`], and
+    /// mutators reapply [`Frame::crc_offset`] to keep the checksum variant.
+    const fn compute_crc(&mut self) {
+        self.inner = (self.inner & !0x0F) | dshot_crc(self.inner >> 4);
+    }
+
+    /// Inverts the CRC in place, as required for bidirectional DShot.
+    const fn invert_crc(&mut self) {
+        self.inner ^= 0x0F;
+    }
+
+    /// Returns the raw [`u16`].
+    #[must_use]
+    pub const fn inner(&self) -> u16 {
+        self.inner
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA.
+    ///
+    /// The duty cycles follow [`BitTiming::DEFAULT`]. Any `max_duty_cycle` up to [`u16::MAX`] is
+    /// supported, so this also works with high-resolution timers.
+    ///
+    /// This contains an extra [`RESET_PULSE`] element to ensure the PWM output gets pulled low at
+    /// the end of the sequence. It can be sliced off if not needed, or use
+    /// [`Frame::duty_cycles_no_reset`] instead.
+    ///
+    /// This is a `const fn`, so waveforms for fixed frames can be computed at compile time.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// static IDLE_WAVEFORM: [u16; DUTY_CYCLES_LEN] = Frame::idle().duty_cycles(100);
+    /// assert_eq!(IDLE_WAVEFORM, Frame::idle().duty_cycles(100));
+    /// ```
+    #[must_use]
+    pub const fn duty_cycles(&self, max_duty_cycle: u16) -> [u16; DUTY_CYCLES_LEN] {
+        self.duty_cycles_with_timing(max_duty_cycle, BitTiming::DEFAULT)
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA, like [`Frame::duty_cycles`], but
+    /// checks that `max_duty_cycle` is large enough to tell zeroes and ones apart.
+    ///
+    /// This catches a `max_duty_cycle` of zero, for example from a timer that has not been
+    /// configured yet.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.try_duty_cycles(100), Ok(frame.duty_cycles(100)));
+    /// assert_eq!(frame.try_duty_cycles(0), Err(DutyError::MaxDutyCycleTooSmall(0)));
+    /// ```
+    pub const fn try_duty_cycles(
+        &self,
+        max_duty_cycle: u16,
+    ) -> Result<[u16; DUTY_CYCLES_LEN], DutyError> {
+        let zero = BitTiming::DEFAULT.zero_duty_cycle(max_duty_cycle);
+        let one = BitTiming::DEFAULT.one_duty_cycle(max_duty_cycle);
+        if zero == RESET_PULSE || zero == one {
+            return Err(DutyError::MaxDutyCycleTooSmall(max_duty_cycle));
+        }
+        Ok(self.duty_cycles(max_duty_cycle))
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA, padded with zeroes to `N` elements.
+    ///
+    /// The padding keeps the PWM output low for `N - 16` bit periods after the frame. `N` needs to
+    /// be at least 16, which is checked at compile time.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let padded: [u16; 20] = frame.duty_cycles_padded(100);
+    /// assert_eq!(padded[..17], frame.duty_cycles(100));
+    /// assert_eq!(padded[17..], [0; 3]);
+    /// ```
+    #[must_use]
+    pub fn duty_cycles_padded<const N: usize>(&self, max_duty_cycle: u16) -> [u16; N] {
+        const { assert!(N >= FRAME_BITS, "duty cycle buffer too short") };
+        let mut rv = [RESET_PULSE; N];
+        rv[..FRAME_BITS].copy_from_slice(&self.duty_cycles_no_reset(max_duty_cycle));
+        rv
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA, computed for each bit by the given
+    /// function.
+    ///
+    /// The function is called with the index of each bit, MSB first, and whether it is a one.
+    /// Like [`Frame::duty_cycles`], this contains an extra element that is always zero.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let duty_cycles = frame.duty_cycles_with(|index, bit| match (index, bit) {
+    ///     (0, true) => 80,
+    ///     (_, true) => 75,
+    ///     (_, false) => 37,
+    /// });
+    /// assert_eq!(duty_cycles[0], 80);
+    /// ```
+    pub fn duty_cycles_with(
+        &self,
+        mut f: impl FnMut(usize, bool) -> u16,
+    ) -> [u16; DUTY_CYCLES_LEN] {
+        let mut rv = [RESET_PULSE; DUTY_CYCLES_LEN];
+        for (index, item) in rv[..FRAME_BITS].iter_mut().enumerate() {
+            *item = f(index, (self.inner >> (FRAME_BITS - 1 - index)) & 1 != 0);
+        }
+        rv
+    }
+
+    /// Returns a vector of duty cycles for use in PWM DMA, with capacity for `N` elements.
+    ///
+    /// This contains the same 17 elements as [`Frame::duty_cycles`], and the remaining capacity
+    /// can be used to append more frames or gaps. `N` needs to be at least 17, which is checked at
+    /// compile time.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let mut duty_cycles = frame.duty_cycles_vec::<40>(100);
+    /// duty_cycles.extend(Frame::motor_stop().duty_cycles(100));
+    /// assert_eq!(duty_cycles.len(), 34);
+    /// ```
+    #[cfg(feature = "heapless")]
+    #[must_use]
+    pub fn duty_cycles_vec<const N: usize>(&self, max_duty_cycle: u16) -> heapless::Vec<u16, N> {
+        const { assert!(N >= DUTY_CYCLES_LEN, "duty cycle buffer too short") };
+        let mut rv = heapless::Vec::new();
+        rv.extend(self.duty_cycles(max_duty_cycle));
+        rv
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA, without the trailing reset element.
+    ///
+    /// Use this if the PWM output gets pulled low by other means after the sequence.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.duty_cycles_no_reset(100), frame.duty_cycles(100)[..16]);
+    /// ```
+    #[must_use]
+    pub const fn duty_cycles_no_reset(&self, max_duty_cycle: u16) -> [u16; FRAME_BITS] {
+        let duty_cycles = self.duty_cycles(max_duty_cycle);
+        let mut rv = [0; FRAME_BITS];
+        let mut index = 0;
+        while index < FRAME_BITS {
+            rv[index] = duty_cycles[index];
+            index += 1;
+        }
+        rv
+    }
+
+    /// Returns the duty cycles as fractions of the bit period, 0.75 for ones and 0.375 for
+    /// zeroes, MSB first.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::command(Command::MotorStop, false);
+    /// assert_eq!(frame.duty_fractions(), [0.375; 16]);
+    /// ```
+    #[cfg(feature = "float")]
+    #[must_use]
+    pub fn duty_fractions(&self) -> [f32; FRAME_BITS] {
+        let mut rv = [0.0; FRAME_BITS];
+        for (shift, item) in rv.iter_mut().rev().enumerate() {
+            *item = if (self.inner >> shift) & 1 == 0 {
+                0.375
+            } else {
+                0.75
+            };
+        }
+        rv
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA on signal lines driven through an
+    /// inverter.
+    ///
+    /// Every element of [`Frame::duty_cycles`] is inverted, so the line idles high after the
+    /// sequence. This is unrelated to the inverted checksum of bidirectional frames.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let inverted = frame.duty_cycles_inverted(100);
+    /// assert_eq!(inverted[0], 100 - frame.duty_cycles(100)[0]);
+    /// assert_eq!(inverted[16], 100);
+    /// ```
+    #[must_use]
+    pub fn duty_cycles_inverted(&self, max_duty_cycle: u16) -> [u16; DUTY_CYCLES_LEN] {
+        let mut rv = self.duty_cycles(max_duty_cycle);
+        for item in rv.iter_mut() {
+            *item = max_duty_cycle - *item;
+        }
+        rv
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA, with the bits in LSB first order.
+    ///
+    /// Only the bits are reversed, the extra zero element stays at the end.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let msb_first = frame.duty_cycles(100);
+    /// let lsb_first = frame.duty_cycles_lsb_first(100);
+    /// assert_eq!(lsb_first[0], msb_first[15]);
+    /// assert_eq!(lsb_first[16], 0);
+    /// ```
+    #[must_use]
+    pub fn duty_cycles_lsb_first(&self, max_duty_cycle: u16) -> [u16; DUTY_CYCLES_LEN] {
+        let mut rv = self.duty_cycles(max_duty_cycle);
+        rv[..FRAME_BITS].reverse();
+        rv
+    }
+
+    /// Returns an iterator over the duty cycles of each bit, MSB first.
+    ///
+    /// This yields the same 16 elements as [`Frame::duty_cycles_no_reset`] without materializing
+    /// an array. Chain a zero onto it if the PWM output needs to be pulled low afterwards.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert!(frame
+    ///     .duty_cycle_iter(100)
+    ///     .chain(core::iter::once(0))
+    ///     .eq(frame.duty_cycles(100)));
+    /// ```
+    pub fn duty_cycle_iter(&self, max_duty_cycle: u16) -> impl Iterator<Item = u16> {
+        self.duty_cycle_iter_with_timing(max_duty_cycle, BitTiming::DEFAULT)
+    }
+
+    /// Returns an iterator over the duty cycles of each bit, MSB first, using the given
+    /// [`BitTiming`] instead of the default ratios.
+    pub fn duty_cycle_iter_with_timing(
+        &self,
+        max_duty_cycle: u16,
+        timing: BitTiming,
+    ) -> impl Iterator<Item = u16> {
+        let value = self.inner;
+        let zero = timing.zero_duty_cycle(max_duty_cycle);
+        let one = timing.one_duty_cycle(max_duty_cycle);
+        (0..FRAME_BITS)
+            .rev()
+            .map(move |shift| if (value >> shift) & 1 == 0 { zero } else { one })
+    }
+
+    /// Returns an array of duty cycles for use in PWM DMA, using the given [`BitTiming`] instead
+    /// of the default ratios.
+    ///
+    /// Like [`Frame::duty_cycles`], this contains an extra element that is always zero.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let duty_cycles = frame.duty_cycles_with_timing(2000, BitTiming::SPEC);
+    /// assert_eq!(duty_cycles[0], 1497);
+    /// ```
+    #[must_use]
+    pub const fn duty_cycles_with_timing(
+        &self,
+        max_duty_cycle: u16,
+        timing: BitTiming,
+    ) -> [u16; DUTY_CYCLES_LEN] {
+        let zero = timing.zero_duty_cycle(max_duty_cycle);
+        let one = timing.one_duty_cycle(max_duty_cycle);
+        let mut rv = [RESET_PULSE; DUTY_CYCLES_LEN];
+        let mut index = 0;
+        while index < FRAME_BITS {
+            rv[index] = if (self.inner >> (FRAME_BITS - 1 - index)) & 1 == 0 {
+                zero
+            } else {
+                one
+            };
+            index += 1;
+        }
+        rv
+    }
+
+    /// Returns how long the line is high for each bit at the given bitrate in nanoseconds, MSB
+    /// first.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::command(Command::MotorStop, false);
+    /// assert_eq!(frame.pulse_ns(Bitrate::Dshot600), [625; 16]);
+    /// ```
+    #[must_use]
+    pub fn pulse_ns(&self, bitrate: Bitrate) -> [u32; FRAME_BITS] {
+        self.high_times(
+            BitTiming::DEFAULT.zero_high_ns(bitrate),
+            BitTiming::DEFAULT.one_high_ns(bitrate),
+        )
+    }
+
+    /// Returns how long the line is high for each bit in nanoseconds, MSB first, given the
+    /// duration of a bit in nanoseconds.
+    ///
+    /// This is independent of any timer resolution, for transmitters that take durations rather
+    /// than duty cycles.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// assert_eq!(frame.duty_cycles_ns(2000)[..2], [1500, 750]);
+    /// ```
+    #[must_use]
+    pub fn duty_cycles_ns(&self, bit_period_ns: u32) -> [u32; FRAME_BITS] {
+        let timing = BitTiming::DEFAULT;
+        let scale = |numerator: u16, denominator: u16| {
+            let denominator = denominator as u64;
+            ((bit_period_ns as u64 * numerator as u64 + denominator / 2) / denominator) as u32
+        };
+        self.high_times(
+            scale(timing.zero_numerator, timing.zero_denominator),
+            scale(timing.one_numerator, timing.one_denominator),
+        )
+    }
+
+    /// Returns `zero` or `one` for each bit, MSB first.
+    fn high_times(&self, zero: u32, one: u32) -> [u32; FRAME_BITS] {
+        let mut rv = [0; FRAME_BITS];
+        for (shift, item) in rv.iter_mut().rev().enumerate() {
+            *item = if (self.inner >> shift) & 1 == 0 {
+                zero
+            } else {
+                one
+            };
+        }
+        rv
+    }
+
+    /// Returns the frame as segments of the line being high or low for a duration in nanoseconds,
+    /// for bit-banging on a GPIO.
+    ///
+    /// Each bit is a high segment followed by a low segment, MSB first. Like
+    /// [`Frame::duty_cycles`], this ends with an extra bit period of the line being low.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let sequence = frame.gpio_sequence(Bitrate::Dshot150);
+    /// assert_eq!(sequence[..2], [(true, 5000), (false, 1667)]);
+    /// assert_eq!(sequence[32], (false, 6667));
+    /// ```
+    #[must_use]
+    pub fn gpio_sequence(&self, bitrate: Bitrate) -> [(bool, u32); 2 * FRAME_BITS + 1] {
+        let period = bitrate.bit_period_ns();
+        let mut rv = [(false, period); 2 * FRAME_BITS + 1];
+        for (segments, high) in rv.chunks_exact_mut(2).zip(self.pulse_ns(bitrate)) {
+            segments[0] = (true, high);
+            segments[1] = (false, period - high);
+        }
+        rv
+    }
+
+    /// Returns the bits of the frame expanded into three-tick symbols, for bit-banging backends
+    /// such as the RP2040 PIO.
+    ///
+    /// Each bit becomes `110` for a one and `100` for a zero, MSB first, for a total of 48 bits
+    /// in the lower bits of the result.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::command(Command::MotorStop, false);
+    /// assert_eq!(frame.bit_symbols(), 0x924924924924);
+    /// ```
+    #[must_use]
+    pub const fn bit_symbols(&self) -> u64 {
+        let mut rv = 0;
+        let mut shift = FRAME_BITS;
+        while shift > 0 {
+            shift -= 1;
+            let symbol = if (self.inner >> shift) & 1 == 0 {
+                0b100
+            } else {
+                0b110
+            };
+            rv = (rv << 3) | symbol;
+        }
+        rv
+    }
+
+    /// Returns the frame as pulses for peripherals that take pulse durations instead of duty
+    /// cycles, such as the RMT peripheral on ESP32.
+    ///
+    /// Each bit is a high level followed by a low level, with durations in ticks of a clock
+    /// running at `clock_hz`.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let pulses = frame.pulses(Bitrate::Dshot600, 80_000_000);
+    /// assert_eq!(pulses[0], Pulse { high_ticks: 100, low_ticks: 33 });
+    /// ```
+    #[must_use]
+    pub fn pulses(&self, bitrate: Bitrate, clock_hz: u32) -> [Pulse; FRAME_BITS] {
+        let ticks_per_bit = (clock_hz / bitrate.timer_hz()).min(u16::MAX as u32) as u16;
+        let mut rv = [Pulse {
+            high_ticks: 0,
+            low_ticks: 0,
+        }; FRAME_BITS];
+        for (item, high_ticks) in rv.iter_mut().zip(self.duty_cycle_iter(ticks_per_bit)) {
+            *item = Pulse {
+                high_ticks,
+                low_ticks: ticks_per_bit - high_ticks,
+            };
+        }
+        rv
+    }
+
+    /// Writes the duty cycles for use in PWM DMA into the given buffer.
+    ///
+    /// This writes the same 17 elements as [`Frame::duty_cycles`] to the start of `out` and
+    /// returns the number of elements written. Returns a [`BufferError`] if `out` is too short.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let mut buffer = [0; 32];
+    /// let frame = Frame::new(1000, false).unwrap();
+    /// let written = frame.write_duty_cycles(100, &mut buffer).unwrap();
+    /// assert_eq!(buffer[..written], frame.duty_cycles(100));
+    /// ```
+    pub fn write_duty_cycles(
+        &self,
+        max_duty_cycle: u16,
+        out: &mut [u16],
+    ) -> Result<usize, BufferError> {
+        let duty_cycles = self.duty_cycles(max_duty_cycle);
+        let Some(out) = out.get_mut(..duty_cycles.len()) else {
+            return Err(BufferError {
+                required: duty_cycles.len(),
+                actual: out.len(),
+            });
+        };
+        out.copy_from_slice(&duty_cycles);
+        Ok(duty_cycles.len())
+    }
+}
+
+impl From<Command> for Frame {
+    /// Creates a new frame with the given [`Command`] without telemetry request.
+    fn from(command: Command) -> Self {
+        Self::command(command, false)
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.payload() {
+            Payload::Throttle(speed) => write!(f, "Frame(speed={speed}")?,
+            Payload::Command(command) => write!(f, "Frame(cmd={command:?}")?,
+            Payload::Reserved(value) => write!(f, "Frame(reserved={value}")?,
+        }
+        write!(
+            f,
+            ", telem={}, crc={:#x})",
+            self.telemetry_enabled(),
+            self.crc()
+        )
+    }
+}
+
+impl Default for Frame {
+    /// Returns a [`Command::MotorStop`] frame without telemetry request.
+    fn default() -> Self {
+        Self::motor_stop()
+    }
+}
+
+/// Returns the part of `out` that holds the duty cycles of `count` frames, or a [`BufferError`]
+/// if `out` is too short, including when the required length overflows.
+fn frames_buffer(out: &mut [u16], count: usize) -> Result<&mut [u16], BufferError> {
+    let actual = out.len();
+    let required = count.saturating_mul(DUTY_CYCLES_LEN);
+    out.get_mut(..required)
+        .ok_or(BufferError { required, actual })
+}
+
+/// Writes the duty cycles of all frames back-to-back into the given buffer.
+///
+/// Each frame takes the same 17 elements as [`Frame::duty_cycles`], including the trailing zero.
+/// Returns the number of elements written, or a [`BufferError`] if `out` is too short, in which
+/// case nothing is written.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frames = [Frame::new(1000, false).unwrap(), Frame::motor_stop()];
+/// let mut buffer = [0; 34];
+/// assert_eq!(encode_frames(&frames, 100, &mut buffer), Ok(34));
+/// assert_eq!(buffer[17..], frames[1].duty_cycles(100));
+/// ```
+pub fn encode_frames(
+    frames: &[Frame],
+    max_duty_cycle: u16,
+    out: &mut [u16],
+) -> Result<usize, BufferError> {
+    let out = frames_buffer(out, frames.len())?;
+    for (frame, chunk) in frames.iter().zip(out.chunks_exact_mut(DUTY_CYCLES_LEN)) {
+        chunk.copy_from_slice(&frame.duty_cycles(max_duty_cycle));
+    }
+    Ok(out.len())
+}
+
+/// Writes the duty cycles of the same frame `count` times back-to-back into the given buffer.
+///
+/// Each repetition takes the same 17 elements as [`Frame::duty_cycles`], including the trailing
+/// zero. Returns the number of elements written, or a [`BufferError`] if `out` is too short, in
+/// which case nothing is written.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frame = Frame::new(1000, false).unwrap();
+/// let mut buffer = [0; 170];
+/// assert_eq!(repeat_frame(&frame, 10, 100, &mut buffer), Ok(170));
+/// assert_eq!(buffer[153..], frame.duty_cycles(100));
+/// ```
+pub fn repeat_frame(
+    frame: &Frame,
+    count: usize,
+    max_duty_cycle: u16,
+    out: &mut [u16],
+) -> Result<usize, BufferError> {
+    let out = frames_buffer(out, count)?;
+    let duty_cycles = frame.duty_cycles(max_duty_cycle);
+    for chunk in out.chunks_exact_mut(DUTY_CYCLES_LEN) {
+        chunk.copy_from_slice(&duty_cycles);
+    }
+    Ok(out.len())
+}
+
+/// Writes the duty cycles of several frames, one per motor, into the given buffer in the given
+/// layout.
+///
+/// Each frame takes the same 17 elements as [`Frame::duty_cycles`]. Returns the number of
+/// elements written, or a [`BufferError`] if `out` is too short, in which case nothing is written.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frames = [Frame::new(1000, false).unwrap(), Frame::motor_stop()];
+/// let mut buffer = [0; 34];
+/// interleave_frames(&frames, 100, BufferLayout::BitMajor, &mut buffer).unwrap();
+/// assert_eq!(buffer[..2], [frames[0].duty_cycles(100)[0], frames[1].duty_cycles(100)[0]]);
+/// ```
+pub fn interleave_frames(
+    frames: &[Frame],
+    max_duty_cycle: u16,
+    layout: BufferLayout,
+    out: &mut [u16],
+) -> Result<usize, BufferError> {
+    if layout == BufferLayout::MotorMajor {
+        return encode_frames(frames, max_duty_cycle, out);
+    }
+    let out = frames_buffer(out, frames.len())?;
+    for (motor, frame) in frames.iter().enumerate() {
+        for (bit, duty_cycle) in frame.duty_cycles(max_duty_cycle).into_iter().enumerate() {
+            out[bit * frames.len() + motor] = duty_cycle;
+        }
+    }
+    Ok(out.len())
+}
+
+/// Returns an iterator over the duty cycles of all frames, each followed by `gap` zeroes.
+///
+/// With a `gap` of one, this yields the same elements as [`encode_frames`]. Longer gaps hold the
+/// PWM output low for longer between frames.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let frames = [Frame::new(1000, false).unwrap(), Frame::motor_stop()];
+/// let mut stream = duty_cycle_stream(frames, 100, 4);
+/// assert!(stream.by_ref().take(16).eq(frames[0].duty_cycle_iter(100)));
+/// assert!(stream.by_ref().take(4).all(|duty_cycle| duty_cycle == 0));
+/// assert_eq!(stream.count(), 20);
+/// ```
+pub fn duty_cycle_stream(
+    frames: impl IntoIterator<Item = Frame>,
+    max_duty_cycle: u16,
+    gap: usize,
+) -> impl Iterator<Item = u16> {
+    frames.into_iter().flat_map(move |frame| {
+        frame
+            .duty_cycle_iter(max_duty_cycle)
+            .chain(core::iter::repeat_n(RESET_PULSE, gap))
+    })
+}
+
+/// Converts a throttle in permille (0-1000) into a speed (0-1999) for [`Frame::new`], rounding to
+/// the nearest value.
+///
+/// Returns [`None`] if the throttle is above 1000.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(throttle_from_permille(500), Some(1000));
+/// assert_eq!(throttle_from_permille(1000), Some(1999));
+/// assert_eq!(throttle_from_permille(1001), None);
+/// ```
+#[must_use]
+pub const fn throttle_from_permille(permille: u16) -> Option<u16> {
+    if permille > 1000 {
+        return None;
+    }
+    Some(((permille as u32 * 1999 + 500) / 1000) as u16)
+}
+
+/// Converts a throttle fraction (0.0-1.0) into a speed (0-1999) for [`Frame::new`], rounding to
+/// the nearest value.
+///
+/// The fraction is rounded to permille first, see [`throttle_from_permille`]. Returns [`None`] if
+/// the fraction is out of bounds or NaN.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(throttle_from_fraction(0.5), Some(1000));
+/// assert_eq!(throttle_from_fraction(1.0), Some(1999));
+/// assert_eq!(throttle_from_fraction(1.1), None);
+/// ```
+#[cfg(feature = "float")]
+#[must_use]
+pub fn throttle_from_fraction(fraction: f32) -> Option<u16> {
+    if !(0.0..=1.0).contains(&fraction) {
+        return None;
+    }
+    throttle_from_permille((fraction * 1000.0 + 0.5) as u16)
+}
+
+/// The direction of rotation in 3D mode, see [`Frame::new_3d`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction3D {
+    Forward,
+    Reverse,
+}
+
+/// The order in which [`interleave_frames`] writes the duty cycles of several frames.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BufferLayout {
+    /// All bits of the first frame, followed by all bits of the second frame, and so on.
+    MotorMajor,
+    /// The first bit of every frame, followed by the second bit of every frame, and so on.
+    BitMajor,
+}
+
+/// A single bit on the wire, as a high level followed by a low level.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pulse {
+    /// The number of ticks the line is high.
+    pub high_ticks: u16,
+    /// The number of ticks the line is low.
+    pub low_ticks: u16,
+}
+
+/// The data carried by a frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Payload {
+    /// A throttle value (0-1999).
+    Throttle(u16),
+    /// A [`Command`].
+    Command(Command),
+    /// A value in one of the unassigned command ranges, see [`Command::is_reserved`].
+    Reserved(u16),
+}
+
+/// Errors that can occur while creating a frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum FrameError {
+    /// The speed is above the maximum of 1999.
+    SpeedOutOfRange(u16),
+    /// The value is reserved or not a command at all.
+    InvalidCommand(u16),
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SpeedOutOfRange(speed) => write!(f, "speed {speed} is out of range (0-1999)"),
+            Self::InvalidCommand(value) => write!(f, "{value} is not a valid command"),
+        }
+    }
+}
+
+impl core::error::Error for FrameError {}
+
+/// Errors that can occur while computing duty cycles.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum DutyError {
+    /// The maximum duty cycle is too small to tell zeroes and ones apart.
+    MaxDutyCycleTooSmall(u16),
+}
+
+impl fmt::Display for DutyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxDutyCycleTooSmall(max) => {
+                write!(f, "maximum duty cycle {max} is too small")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DutyError {}
+
+/// Errors that can occur while decoding duty cycles into a frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// There are fewer duty cycles than bits in a frame.
+    BufferTooShort(BufferError),
+    /// The decoded checksum does not match the data.
+    InvalidCrc(CrcError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooShort(error) => error.fmt(f),
+            Self::InvalidCrc(error) => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::BufferTooShort(error) => Some(error),
+            Self::InvalidCrc(error) => Some(error),
+        }
+    }
+}
+
+impl From<BufferError> for DecodeError {
+    fn from(error: BufferError) -> Self {
+        Self::BufferTooShort(error)
+    }
+}
+
+impl From<CrcError> for DecodeError {
+    fn from(error: CrcError) -> Self {
+        Self::InvalidCrc(error)
+    }
+}
+
+/// Error returned when a buffer is too short to hold the requested output.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BufferError {
+    /// The number of elements required.
+    pub required: usize,
+    /// The number of elements available.
+    pub actual: usize,
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "buffer too short: {} elements required, {} available",
+            self.required, self.actual
+        )
+    }
+}
+
+impl core::error::Error for BufferError {}
+
+/// Error returned when a frame's stored checksum does not match its data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CrcError {
+    /// The checksum computed from the first 12 bits.
+    pub expected: u16,
+    /// The checksum found in the last four bits.
+    pub actual: u16,
+}
+
+impl fmt::Display for CrcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid checksum: expected {:#x}, found {:#x}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl core::error::Error for CrcError {}
+
+/// Fixed commands that occupy the lower 48 speed values.
+///
+/// Some commands need to be sent multiple times to be acted upon to prevent accidental bit-flips
+/// wreaking havoc.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Command {
+    MotorStop = 0,
+    /// Wait at least 260ms before next command.
+    Beep1,
+    /// Wait at least 260ms before next command.
+    Beep2,
+    /// Wait at least 260ms before next command.
+    Beep3,
+    /// Wait at least 260ms before next command.
+    Beep4,
+    /// Wait at least 260ms before next command.
+    Beep5,
+    /// Wait at least 12ms before next command.
+    ESCInfo,
+    /// Needs 6 transmissions.
+    SpinDirection1,
+    /// Needs 6 transmissions.
+    SpinDirection2,
+    /// Needs 6 transmissions.
+    ThreeDModeOn,
+    /// Needs 6 transmissions.
+    ThreeDModeOff,
+    SettingsRequest,
+    /// Needs 6 transmissions. Wait at least 35ms before next command.
+    SettingsSave,
+    /// Needs 6 transmissions.
+    ExtendedTelemetryEnable,
+    /// Needs 6 transmissions.
+    ExtendedTelemetryDisable,
+
+    // 15-19 are unassigned.
+    /// Needs 6 transmissions.
+    SpinDirectionNormal = 20,
+    /// Needs 6 transmissions.
+    SpinDirectionReversed,
+    Led0On,
+    Led1On,
+    Led2On,
+    Led3On,
+    Led0Off,
+    Led1Off,
+    Led2Off,
+    Led3Off,
+    AudioStreamModeToggle,
+    SilentModeToggle,
+    /// Needs 6 transmissions. Enables individual signal line commands.
+    SignalLineTelemetryEnable,
+    /// Needs 6 transmissions. Disables individual signal line commands.
+    SignalLineTelemetryDisable,
+    /// Needs 6 transmissions. Enables individual signal line commands.
+    SignalLineContinuousERPMTelemetry,
+    /// Needs 6 transmissions. Enables individual signal line commands.
+    SignalLineContinuousERPMPeriodTelemetry,
+
+    // 36-41 are unassigned.
+    /// 1ºC per LSB.
+    SignalLineTemperatureTelemetry = 42,
+    /// 10mV per LSB, 40.95V max.
+    SignalLineVoltageTelemetry,
+    /// 100mA per LSB, 409.5A max.
+    SignalLineCurrentTelemetry,
+    /// 10mAh per LSB, 40.95Ah max.
+    SignalLineConsumptionTelemetry,
+    /// 100erpm per LSB, 409500erpm max.
+    SignalLineERPMTelemetry,
+    /// 16us per LSB, 65520us max.
+    SignalLineERPMPeriodTelemetry,
+}
+
+impl Command {
+    /// Misspelled alias of [`Command::SpinDirectionReversed`].
+    #[deprecated(note = "use `Command::SpinDirectionReversed` instead")]
+    #[allow(non_upper_case_globals)]
+    pub const SpinDirectonReversed: Self = Self::SpinDirectionReversed;
+
+    /// Returns all assigned commands, in order of their values.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Command::all().len(), 37);
+    /// assert_eq!(Command::all()[0], Command::MotorStop);
+    /// ```
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::MotorStop,
+            Self::Beep1,
+            Self::Beep2,
+            Self::Beep3,
+            Self::Beep4,
+            Self::Beep5,
+            Self::ESCInfo,
+            Self::SpinDirection1,
+            Self::SpinDirection2,
+            Self::ThreeDModeOn,
+            Self::ThreeDModeOff,
+            Self::SettingsRequest,
+            Self::SettingsSave,
+            Self::ExtendedTelemetryEnable,
+            Self::ExtendedTelemetryDisable,
+            Self::SpinDirectionNormal,
+            Self::SpinDirectionReversed,
+            Self::Led0On,
+            Self::Led1On,
+            Self::Led2On,
+            Self::Led3On,
+            Self::Led0Off,
+            Self::Led1Off,
+            Self::Led2Off,
+            Self::Led3Off,
+            Self::AudioStreamModeToggle,
+            Self::SilentModeToggle,
+            Self::SignalLineTelemetryEnable,
+            Self::SignalLineTelemetryDisable,
+            Self::SignalLineContinuousERPMTelemetry,
+            Self::SignalLineContinuousERPMPeriodTelemetry,
+            Self::SignalLineTemperatureTelemetry,
+            Self::SignalLineVoltageTelemetry,
+            Self::SignalLineCurrentTelemetry,
+            Self::SignalLineConsumptionTelemetry,
+            Self::SignalLineERPMTelemetry,
+            Self::SignalLineERPMPeriodTelemetry,
+        ]
+    }
+
+    /// Returns whether the given raw value falls in one of the unassigned command ranges.
+    ///
+    /// Every [`Command`] variant is a valid command, so this is only useful for checking raw
+    /// values before turning them into frames, e.g. with [`Frame::from_raw`].
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert!(Command::is_reserved(15));
+    /// assert!(!Command::is_reserved(Command::SpinDirectionNormal as u16));
+    /// ```
+    #[must_use]
+    pub const fn is_reserved(value: u16) -> bool {
+        matches!(value, 15..=19 | 36..=41)
+    }
+
+    /// Returns the command with the given raw value, or [`None`] if the value is reserved or not a
+    /// command at all.
+    ///
+    /// This is the inverse of casting a command with `as u16`.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Command::from_u16(Command::Beep3 as u16), Some(Command::Beep3));
+    /// assert_eq!(Command::from_u16(15), None);
+    /// assert_eq!(Command::from_u16(48), None);
+    /// ```
+    #[must_use]
+    pub const fn from_u16(value: u16) -> Option<Self> {
+        match value {
+            0 => Some(Self::MotorStop),
+            1 => Some(Self::Beep1),
+            2 => Some(Self::Beep2),
+            3 => Some(Self::Beep3),
+            4 => Some(Self::Beep4),
+            5 => Some(Self::Beep5),
+            6 => Some(Self::ESCInfo),
+            7 => Some(Self::SpinDirection1),
+            8 => Some(Self::SpinDirection2),
+            9 => Some(Self::ThreeDModeOn),
+            10 => Some(Self::ThreeDModeOff),
+            11 => Some(Self::SettingsRequest),
+            12 => Some(Self::SettingsSave),
+            13 => Some(Self::ExtendedTelemetryEnable),
+            14 => Some(Self::ExtendedTelemetryDisable),
+            20 => Some(Self::SpinDirectionNormal),
+            21 => Some(Self::SpinDirectionReversed),
+            22 => Some(Self::Led0On),
+            23 => Some(Self::Led1On),
+            24 => Some(Self::Led2On),
+            25 => Some(Self::Led3On),
+            26 => Some(Self::Led0Off),
+            27 => Some(Self::Led1Off),
+            28 => Some(Self::Led2Off),
+            29 => Some(Self::Led3Off),
+            30 => Some(Self::AudioStreamModeToggle),
+            31 => Some(Self::SilentModeToggle),
+            32 => Some(Self::SignalLineTelemetryEnable),
+            33 => Some(Self::SignalLineTelemetryDisable),
+            34 => Some(Self::SignalLineContinuousERPMTelemetry),
+            35 => Some(Self::SignalLineContinuousERPMPeriodTelemetry),
+            42 => Some(Self::SignalLineTemperatureTelemetry),
+            43 => Some(Self::SignalLineVoltageTelemetry),
+            44 => Some(Self::SignalLineCurrentTelemetry),
+            45 => Some(Self::SignalLineConsumptionTelemetry),
+            46 => Some(Self::SignalLineERPMTelemetry),
+            47 => Some(Self::SignalLineERPMPeriodTelemetry),
+            _ => None,
+        }
+    }
+
+    /// Returns a short human-readable description of the command, including how often to send
+    /// it and how long to wait afterwards where that matters.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Command::MotorStop.description(), "Motor stop");
+    /// assert_eq!(
+    ///     Command::SpinDirection1.description(),
+    ///     "Spin direction 1 (needs 6 transmissions)"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::MotorStop => "Motor stop",
+            Self::Beep1 => "Beep 1 (wait 260ms)",
+            Self::Beep2 => "Beep 2 (wait 260ms)",
+            Self::Beep3 => "Beep 3 (wait 260ms)",
+            Self::Beep4 => "Beep 4 (wait 260ms)",
+            Self::Beep5 => "Beep 5 (wait 260ms)",
+            Self::ESCInfo => "ESC info (wait 12ms)",
+            Self::SpinDirection1 => "Spin direction 1 (needs 6 transmissions)",
+            Self::SpinDirection2 => "Spin direction 2 (needs 6 transmissions)",
+            Self::ThreeDModeOn => "3D mode on (needs 6 transmissions)",
+            Self::ThreeDModeOff => "3D mode off (needs 6 transmissions)",
+            Self::SettingsRequest => "Settings request",
+            Self::SettingsSave => "Settings save (needs 6 transmissions, wait 35ms)",
+            Self::ExtendedTelemetryEnable => "Extended telemetry enable (needs 6 transmissions)",
+            Self::ExtendedTelemetryDisable => "Extended telemetry disable (needs 6 transmissions)",
+            Self::SpinDirectionNormal => "Spin direction normal (needs 6 transmissions)",
+            Self::SpinDirectionReversed => "Spin direction reversed (needs 6 transmissions)",
+            Self::Led0On => "LED 0 on",
+            Self::Led1On => "LED 1 on",
+            Self::Led2On => "LED 2 on",
+            Self::Led3On => "LED 3 on",
+            Self::Led0Off => "LED 0 off",
+            Self::Led1Off => "LED 1 off",
+            Self::Led2Off => "LED 2 off",
+            Self::Led3Off => "LED 3 off",
+            Self::AudioStreamModeToggle => "Audio stream mode toggle",
+            Self::SilentModeToggle => "Silent mode toggle",
+            Self::SignalLineTelemetryEnable => {
+                "Signal line telemetry enable (needs 6 transmissions)"
+            }
+            Self::SignalLineTelemetryDisable => {
+                "Signal line telemetry disable (needs 6 transmissions)"
+            }
+            Self::SignalLineContinuousERPMTelemetry => {
+                "Signal line continuous eRPM telemetry (needs 6 transmissions)"
+            }
+            Self::SignalLineContinuousERPMPeriodTelemetry => {
+                "Signal line continuous eRPM period telemetry (needs 6 transmissions)"
+            }
+            Self::SignalLineTemperatureTelemetry => "Signal line temperature telemetry",
+            Self::SignalLineVoltageTelemetry => "Signal line voltage telemetry",
+            Self::SignalLineCurrentTelemetry => "Signal line current telemetry",
+            Self::SignalLineConsumptionTelemetry => "Signal line consumption telemetry",
+            Self::SignalLineERPMTelemetry => "Signal line eRPM telemetry",
+            Self::SignalLineERPMPeriodTelemetry => "Signal line eRPM period telemetry",
+        }
+    }
+
+    /// Returns how many times the command needs to be transmitted to be acted upon.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Command::Beep1.required_repetitions(), 1);
+    /// assert_eq!(Command::SettingsSave.required_repetitions(), 6);
+    /// ```
+    #[must_use]
+    pub const fn required_repetitions(&self) -> u8 {
+        match self {
+            Self::SpinDirection1
+            | Self::SpinDirection2
+            | Self::ThreeDModeOn
+            | Self::ThreeDModeOff
+            | Self::SettingsSave
+            | Self::ExtendedTelemetryEnable
+            | Self::ExtendedTelemetryDisable
+            | Self::SpinDirectionNormal
+            | Self::SpinDirectionReversed
+            | Self::SignalLineTelemetryEnable
+            | Self::SignalLineTelemetryDisable
+            | Self::SignalLineContinuousERPMTelemetry
+            | Self::SignalLineContinuousERPMPeriodTelemetry => 6,
+            _ => 1,
+        }
+    }
+
+    /// Returns the minimum time in microseconds to wait after the command before sending the next
+    /// one, if there is any.
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(Command::Beep1.min_delay_after_us(), Some(260_000));
+    /// assert_eq!(Command::MotorStop.min_delay_after_us(), None);
+    /// ```
+    #[must_use]
+    pub const fn min_delay_after_us(&self) -> Option<u32> {
+        match self {
+            Self::Beep1 | Self::Beep2 | Self::Beep3 | Self::Beep4 | Self::Beep5 => Some(260_000),
+            Self::ESCInfo => Some(12_000),
+            Self::SettingsSave => Some(35_000),
+            _ => None,
+        }
+    }
+
+    /// Returns how the command needs to be transmitted, combining
+    /// [`Command::required_repetitions`] and [`Command::min_delay_after_us`].
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// assert_eq!(
+    ///     Command::SettingsSave.transmission_requirement(),
+    ///     TransmissionRequirement::WithDelay {
+    ///         count: 6,
+    ///         delay_us: 35_000
+    ///     }
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn transmission_requirement(&self) -> TransmissionRequirement {
+        match (self.required_repetitions(), self.min_delay_after_us()) {
+            (count, Some(delay_us)) => TransmissionRequirement::WithDelay { count, delay_us },
+            (1, None) => TransmissionRequirement::Once,
+            (count, None) => TransmissionRequirement::Repeated(count),
+        }
+    }
+}
+
+/// How a [`Command`] needs to be transmitted to be acted upon.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransmissionRequirement {
+    /// Send the command once.
+    Once,
+    /// Send the command the given number of times.
+    Repeated(u8),
+    /// Send the command `count` times, then wait at least `delay_us` microseconds before the next
+    /// command.
+    WithDelay { count: u8, delay_us: u32 },
+}
+
+impl TryFrom<u16> for Command {
+    type Error = FrameError;
+
+    /// See [`Command::from_u16`].
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        Self::from_u16(value).ok_or(FrameError::InvalidCommand(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_DUTY_CYCLE: u16 = 100;
+    const ZERO: u16 = 37;
+    const ONE: u16 = 75;
+
+    #[test]
+    fn duty_cycles_works() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(
+            frame.duty_cycles(MAX_DUTY_CYCLE),
+            [
+                ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ONE, ONE, ONE, ZERO, ZERO, ONE, ZERO,
+                ZERO, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn duty_cycles_at_zero() {
+        let frame = Frame::command(Command::MotorStop, false);
+        assert_eq!(
+            frame.duty_cycles(MAX_DUTY_CYCLE),
+            [
+                ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO,
+                ZERO, ZERO, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn duty_cycles_with_high_resolution_timers() {
+        let frame = Frame::new(999, false).unwrap();
+        let duty_cycles = frame.duty_cycles(60_000);
+        assert_eq!(duty_cycles[..2], [45_000, 22_500]);
+        let duty_cycles = frame.duty_cycles(u16::MAX);
+        assert_eq!(duty_cycles[..2], [49_151, 24_576]);
+    }
+
+    #[test]
+    fn duty_cycles_with_matches_default_duty_cycles() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(
+            frame.duty_cycles_with(|_, bit| if bit { ONE } else { ZERO }),
+            frame.duty_cycles(MAX_DUTY_CYCLE)
+        );
+    }
+
+    #[test]
+    fn duty_cycles_no_reset_omits_reset_element() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(
+            frame.duty_cycles_no_reset(MAX_DUTY_CYCLE),
+            [
+                ONE, ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ZERO, ONE, ONE, ONE, ZERO, ZERO, ONE, ZERO,
+                ZERO
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn duty_fractions_are_msb_first() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(frame.duty_fractions()[..3], [0.75, 0.375, 0.375]);
+    }
+
+    #[test]
+    fn const_duty_cycles_match_iterator() {
+        const WAVEFORM: [u16; DUTY_CYCLES_LEN] = Frame::idle().duty_cycles(MAX_DUTY_CYCLE);
+        assert!(WAVEFORM[..FRAME_BITS]
+            .iter()
+            .copied()
+            .eq(Frame::idle().duty_cycle_iter(MAX_DUTY_CYCLE)));
+    }
+
+    #[test]
+    fn try_duty_cycles_rejects_tiny_max() {
+        let frame = Frame::new(1000, false).unwrap();
+        assert_eq!(
+            frame.try_duty_cycles(2),
+            Err(DutyError::MaxDutyCycleTooSmall(2))
+        );
+        assert_eq!(frame.try_duty_cycles(3), Ok(frame.duty_cycles(3)));
+    }
+
+    #[test]
+    fn duty_cycles_inverted_idles_high() {
+        let frame = Frame::command(Command::MotorStop, false);
+        let mut expected = [MAX_DUTY_CYCLE - ZERO; 17];
+        expected[16] = MAX_DUTY_CYCLE;
+        assert_eq!(frame.duty_cycles_inverted(MAX_DUTY_CYCLE), expected);
+    }
+
+    #[test]
+    fn duty_cycles_lsb_first_keeps_reset_element_last() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(
+            frame.duty_cycles_lsb_first(MAX_DUTY_CYCLE),
+            [
+                ZERO, ZERO, ONE, ZERO, ZERO, ONE, ONE, ONE, ZERO, ONE, ZERO, ZERO, ZERO, ZERO,
+                ZERO, ONE, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn duty_cycle_iter_yields_msb_first() {
+        let frame = Frame::new(999, false).unwrap();
+        let mut iter = frame.duty_cycle_iter(MAX_DUTY_CYCLE);
+        assert_eq!(iter.next(), Some(ONE));
+        assert_eq!(iter.next(), Some(ZERO));
+        assert_eq!(iter.count(), 14);
+    }
+
+    #[test]
+    fn duty_cycles_padded_without_padding() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(
+            frame.duty_cycles_padded::<16>(MAX_DUTY_CYCLE),
+            frame.duty_cycles_no_reset(MAX_DUTY_CYCLE)
+        );
+    }
+
+    #[test]
+    fn pulses_cover_whole_bit_periods() {
+        let frame = Frame::new(999, false).unwrap();
+        let pulses = frame.pulses(Bitrate::Dshot150, 15_000_000);
+        assert_eq!(
+            pulses[..2],
+            [
+                Pulse {
+                    high_ticks: ONE,
+                    low_ticks: 25
+                },
+                Pulse {
+                    high_ticks: ZERO,
+                    low_ticks: 63
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn duty_cycles_with_custom_timing() {
+        let timing = BitTiming {
+            one_numerator: 2,
+            one_denominator: 3,
+            zero_numerator: 1,
+            zero_denominator: 3,
+        };
+        let frame = Frame::new(999, false).unwrap();
+        let duty_cycles = frame.duty_cycles_with_timing(90, timing);
+        assert_eq!(duty_cycles[..3], [60, 30, 30]);
+        assert_eq!(duty_cycles[16], 0);
+    }
+
+    #[test]
+    fn pulse_ns_are_msb_first() {
+        let frame = Frame::new(999, false).unwrap();
+        let pulses = frame.pulse_ns(Bitrate::Dshot150);
+        assert_eq!(pulses[..3], [5000, 2500, 2500]);
+        assert_eq!(pulses[15], 2500);
+    }
+
+    #[test]
+    fn duty_cycles_ns_rounds_to_nearest() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(frame.duty_cycles_ns(1667)[..2], [1250, 625]);
+        assert_eq!(frame.duty_cycles_ns(u32::MAX)[0], 3_221_225_471);
+    }
+
+    #[test]
+    fn gpio_sequence_alternates_levels() {
+        let frame = Frame::motor_stop();
+        let sequence = frame.gpio_sequence(Bitrate::Dshot600);
+        for segments in sequence[..32].chunks_exact(2) {
+            assert_eq!(segments, [(true, 625), (false, 1042)]);
+        }
+    }
+
+    #[test]
+    fn bit_symbols_are_msb_first() {
+        let frame = Frame::new(999, false).unwrap();
+        let symbols = frame.bit_symbols();
+        assert_eq!(symbols >> 48, 0);
+        assert_eq!(symbols >> 42, 0b110_100);
+        assert_eq!(symbols & 0b111_111, 0b100_100);
+    }
+
+    #[test]
+    fn write_duty_cycles_works() {
+        let frame = Frame::new(999, false).unwrap();
+        let mut buffer = [u16::MAX; 18];
+        assert_eq!(frame.write_duty_cycles(MAX_DUTY_CYCLE, &mut buffer), Ok(17));
+        assert_eq!(buffer[..17], frame.duty_cycles(MAX_DUTY_CYCLE));
+        assert_eq!(buffer[17], u16::MAX);
+    }
+
+    #[test]
+    fn write_duty_cycles_rejects_short_buffers() {
+        let frame = Frame::new(999, false).unwrap();
+        assert_eq!(
+            frame.write_duty_cycles(MAX_DUTY_CYCLE, &mut [0; 16]),
+            Err(BufferError {
+                required: 17,
+                actual: 16
+            })
+        );
+    }
+
+    #[test]
+    fn encode_frames_rejects_short_buffers() {
+        let frames = [Frame::motor_stop(); 4];
+        let mut buffer = [u16::MAX; 67];
+        assert_eq!(
+            encode_frames(&frames, MAX_DUTY_CYCLE, &mut buffer),
+            Err(BufferError {
+                required: 68,
+                actual: 67
+            })
+        );
+        assert!(buffer.iter().all(|&duty_cycle| duty_cycle == u16::MAX));
+    }
+
+    #[test]
+    fn wire_duration_covers_all_bits() {
+        assert_eq!(Frame::wire_duration_ns(Bitrate::Dshot150), 106_667);
+        assert_eq!(Frame::wire_duration_ns(Bitrate::Dshot300), 53_333);
+        assert_eq!(Frame::wire_duration_ns(Bitrate::Dshot1200), 13_333);
+    }
+
+    #[test]
+    fn repeat_frame_rejects_short_buffers() {
+        let frame = Frame::motor_stop();
+        assert_eq!(
+            repeat_frame(&frame, 3, MAX_DUTY_CYCLE, &mut [0; 50]),
+            Err(BufferError {
+                required: 51,
+                actual: 50
+            })
+        );
+        assert_eq!(repeat_frame(&frame, 0, MAX_DUTY_CYCLE, &mut []), Ok(0));
+    }
+
+    #[test]
+    fn interleave_frames_bit_major() {
+        let frames = [
+            Frame::new(999, false).unwrap(),
+            Frame::motor_stop(),
+            Frame::new(999, false).unwrap(),
+        ];
+        let mut buffer = [u16::MAX; 51];
+        assert_eq!(
+            interleave_frames(&frames, MAX_DUTY_CYCLE, BufferLayout::BitMajor, &mut buffer),
+            Ok(51)
+        );
+        assert_eq!(buffer[..6], [ONE, ZERO, ONE, ZERO, ZERO, ZERO]);
+        assert_eq!(buffer[48..], [0, 0, 0]);
+    }
+
+    #[test]
+    fn interleave_frames_rejects_short_buffers() {
+        let frames = [Frame::motor_stop(); 2];
+        assert_eq!(
+            interleave_frames(
+                &frames,
+                MAX_DUTY_CYCLE,
+                BufferLayout::BitMajor,
+                &mut [0; 33]
+            ),
+            Err(BufferError {
+                required: 34,
+                actual: 33
+            })
+        );
+    }
+
+    #[test]
+    fn duty_cycle_stream_matches_encode_frames() {
+        let frames = [Frame::new(999, false).unwrap(), Frame::motor_stop()];
+        let mut buffer = [0; 34];
+        encode_frames(&frames, MAX_DUTY_CYCLE, &mut buffer).unwrap();
+        assert!(duty_cycle_stream(frames, MAX_DUTY_CYCLE, 1).eq(buffer));
+    }
+
+    #[test]
+    fn frame_constructs_correctly() {
+        let frame = Frame::new(998, false).unwrap();
+        assert_eq!(frame.speed(), 998);
+        assert!(!frame.telemetry_enabled());
+        assert_eq!(frame.crc(), 0x06);
+    }
+
+    #[test]
     fn frame_constructs_correctly_with_telemetry() {
         let frame = Frame::new(998, true).unwrap();
         assert_eq!(frame.speed(), 998);
@@ -252,8 +2203,557 @@ mod tests {
         assert_eq!(frame.speed(), 50);
     }
 
+    #[test]
+    fn frame_covers_full_throttle_range() {
+        assert_eq!(Frame::new(0, false).unwrap().throttle_field(), 48);
+        assert_eq!(Frame::new(1999, false).unwrap().throttle_field(), 2047);
+    }
+
     #[test]
     fn frame_rejects_invalid_speed_values() {
         assert!(Frame::new(2000, false).is_none())
     }
+
+    #[test]
+    fn constructors_work_in_const_context() {
+        const TABLE: [Frame; 2] = [
+            Frame::command(Command::MotorStop, false),
+            match Frame::try_new(998, false) {
+                Ok(frame) => frame,
+                Err(_) => panic!(),
+            },
+        ];
+        assert_eq!(TABLE[0].inner(), 0);
+        assert_eq!(TABLE[1].crc(), 0x06);
+    }
+
+    #[test]
+    fn new_with_max_respects_both_limits() {
+        assert!(Frame::new_with_max(1500, 1500, false).is_some());
+        assert!(Frame::new_with_max(2000, u16::MAX, false).is_none());
+    }
+
+    #[test]
+    fn new_clamped_saturates_speed() {
+        assert_eq!(
+            Frame::new_clamped(u16::MAX, true),
+            Frame::new(1999, true).unwrap()
+        );
+        assert_eq!(
+            Frame::new_clamped(998, false),
+            Frame::new(998, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn try_new_reports_invalid_speed_values() {
+        assert_eq!(
+            Frame::try_new(2000, false).unwrap_err(),
+            FrameError::SpeedOutOfRange(2000)
+        );
+    }
+
+    #[test]
+    fn display_shows_payload() {
+        extern crate std;
+        use std::string::ToString;
+
+        assert_eq!(
+            Frame::new(998, false).unwrap().to_string(),
+            "Frame(speed=998, telem=false, crc=0x6)"
+        );
+        assert_eq!(
+            Frame::command(Command::MotorStop, true).to_string(),
+            "Frame(cmd=MotorStop, telem=true, crc=0x1)"
+        );
+    }
+
+    #[test]
+    fn frame_from_command_disables_telemetry() {
+        let frame = Frame::from(Command::ESCInfo);
+        assert_eq!(frame, Frame::command(Command::ESCInfo, false));
+    }
+
+    #[test]
+    fn default_frame_stops_the_motor() {
+        assert_eq!(Frame::default(), Frame::command(Command::MotorStop, false));
+    }
+
+    #[test]
+    fn command_burst_repeats_the_same_frame() {
+        let frame = Frame::command(Command::ThreeDModeOn, true);
+        let mut burst = Frame::command_burst(Command::ThreeDModeOn, true);
+        assert!(burst.clone().all(|f| f == frame));
+        assert_eq!(burst.nth(5), Some(frame));
+        assert_eq!(burst.next(), None);
+        assert_eq!(Frame::command_burst(Command::Beep1, false).count(), 1);
+    }
+
+    #[test]
+    fn command_shortcuts() {
+        assert_eq!(Frame::motor_stop().inner(), 0);
+        assert_eq!(
+            Frame::beep(1).unwrap().payload(),
+            Payload::Command(Command::Beep1)
+        );
+        assert_eq!(
+            Frame::beep(5).unwrap().payload(),
+            Payload::Command(Command::Beep5)
+        );
+        assert!(Frame::beep(0).is_none());
+        assert_eq!(
+            Frame::save_settings().payload(),
+            Payload::Command(Command::SettingsSave)
+        );
+    }
+
+    #[test]
+    fn bidirectional_frame_inverts_crc() {
+        let frame = Frame::new_bidirectional(998, false).unwrap();
+        assert_eq!(frame.speed(), 998);
+        assert!(!frame.telemetry_enabled());
+        assert_eq!(frame.crc(), 0x09);
+    }
+
+    #[test]
+    fn bidirectional_duty_cycles_reflect_inverted_crc() {
+        let frame = Frame::command_bidirectional(Command::MotorStop, false);
+        assert_eq!(
+            frame.duty_cycles(MAX_DUTY_CYCLE),
+            [
+                ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ONE, ONE,
+                ONE, ONE, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn frames_with_the_same_data_are_equal() {
+        assert_eq!(Frame::new(998, false), Frame::new(998, false));
+        assert_ne!(Frame::new(998, false), Frame::new(998, true));
+        assert_ne!(Frame::new(998, false), Frame::new_bidirectional(998, false));
+    }
+
+    #[test]
+    fn command_descriptions_mention_repetitions() {
+        for value in 0..48 {
+            if let Some(command) = Command::from_u16(value) {
+                assert_eq!(
+                    command.description().contains("needs 6 transmissions"),
+                    command.required_repetitions() == 6
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn misspelled_spin_direction_alias_still_works() {
+        assert_eq!(
+            Command::SpinDirectonReversed,
+            Command::SpinDirectionReversed
+        );
+        assert!(matches!(
+            Command::SpinDirectionReversed,
+            Command::SpinDirectonReversed
+        ));
+    }
+
+    #[test]
+    fn transmission_requirements() {
+        assert_eq!(
+            Command::MotorStop.transmission_requirement(),
+            TransmissionRequirement::Once
+        );
+        assert_eq!(
+            Command::ThreeDModeOn.transmission_requirement(),
+            TransmissionRequirement::Repeated(6)
+        );
+        assert_eq!(
+            Command::Beep2.transmission_requirement(),
+            TransmissionRequirement::WithDelay {
+                count: 1,
+                delay_us: 260_000
+            }
+        );
+    }
+
+    #[test]
+    fn command_timing_matches_documentation() {
+        assert_eq!(Command::ThreeDModeOn.required_repetitions(), 6);
+        assert_eq!(Command::SettingsRequest.required_repetitions(), 1);
+        assert_eq!(Command::ESCInfo.min_delay_after_us(), Some(12_000));
+        assert_eq!(Command::SettingsSave.min_delay_after_us(), Some(35_000));
+    }
+
+    #[test]
+    fn reserved_command_ranges() {
+        assert!(!Command::is_reserved(
+            Command::ExtendedTelemetryDisable as u16
+        ));
+        assert!((15..=19).all(Command::is_reserved));
+        assert!((36..=41).all(Command::is_reserved));
+        assert!(!Command::is_reserved(
+            Command::SignalLineTemperatureTelemetry as u16
+        ));
+    }
+
+    #[test]
+    fn command_discriminants_match_spec() {
+        let commands = [
+            (Command::MotorStop, 0),
+            (Command::Beep1, 1),
+            (Command::Beep2, 2),
+            (Command::Beep3, 3),
+            (Command::Beep4, 4),
+            (Command::Beep5, 5),
+            (Command::ESCInfo, 6),
+            (Command::SpinDirection1, 7),
+            (Command::SpinDirection2, 8),
+            (Command::ThreeDModeOn, 9),
+            (Command::ThreeDModeOff, 10),
+            (Command::SettingsRequest, 11),
+            (Command::SettingsSave, 12),
+            (Command::ExtendedTelemetryEnable, 13),
+            (Command::ExtendedTelemetryDisable, 14),
+            (Command::SpinDirectionNormal, 20),
+            (Command::SpinDirectionReversed, 21),
+            (Command::Led0On, 22),
+            (Command::Led1On, 23),
+            (Command::Led2On, 24),
+            (Command::Led3On, 25),
+            (Command::Led0Off, 26),
+            (Command::Led1Off, 27),
+            (Command::Led2Off, 28),
+            (Command::Led3Off, 29),
+            (Command::AudioStreamModeToggle, 30),
+            (Command::SilentModeToggle, 31),
+            (Command::SignalLineTelemetryEnable, 32),
+            (Command::SignalLineTelemetryDisable, 33),
+            (Command::SignalLineContinuousERPMTelemetry, 34),
+            (Command::SignalLineContinuousERPMPeriodTelemetry, 35),
+            (Command::SignalLineTemperatureTelemetry, 42),
+            (Command::SignalLineVoltageTelemetry, 43),
+            (Command::SignalLineCurrentTelemetry, 44),
+            (Command::SignalLineConsumptionTelemetry, 45),
+            (Command::SignalLineERPMTelemetry, 46),
+            (Command::SignalLineERPMPeriodTelemetry, 47),
+        ];
+        for (command, value) in commands {
+            assert_eq!(command as u16, value, "{command:?}");
+        }
+    }
+
+    #[test]
+    fn all_commands_are_assigned() {
+        let assigned = (0..48).filter(|&value| !Command::is_reserved(value));
+        assert!(assigned.eq(Command::all().iter().map(|&command| command as u16)));
+    }
+
+    #[test]
+    fn command_from_u16_round_trips() {
+        for value in 0..48 {
+            match Command::from_u16(value) {
+                Some(command) => assert_eq!(command as u16, value),
+                None => assert!(Command::is_reserved(value)),
+            }
+        }
+        assert_eq!(Command::from_u16(48), None);
+    }
+
+    #[test]
+    fn same_payload_ignores_telemetry_and_crc() {
+        let frame = Frame::new(998, false).unwrap();
+        assert!(frame.same_payload(&Frame::new_bidirectional(998, true).unwrap()));
+        assert!(!frame.same_payload(&Frame::new(999, false).unwrap()));
+    }
+
+    #[test]
+    fn payload_distinguishes_throttle_and_commands() {
+        assert_eq!(
+            Frame::new(0, false).unwrap().payload(),
+            Payload::Throttle(0)
+        );
+        assert_eq!(
+            Frame::command(Command::SignalLineERPMPeriodTelemetry, true).payload(),
+            Payload::Command(Command::SignalLineERPMPeriodTelemetry)
+        );
+        assert_eq!(
+            Frame::from_raw(0x01EF).unwrap().payload(),
+            Payload::Reserved(15)
+        );
+    }
+
+    #[test]
+    fn speed_does_not_underflow_for_commands() {
+        let frame = Frame::command(Command::MotorStop, false);
+        assert_eq!(frame.speed(), 0);
+        assert_eq!(frame.try_speed(), None);
+    }
+
+    #[test]
+    fn frame_3d_signed_scales_magnitude() {
+        assert_eq!(Frame::new_3d_signed(1000, false).unwrap().speed(), 1999);
+        assert_eq!(Frame::new_3d_signed(1, false).unwrap().speed(), 1001);
+        assert_eq!(Frame::new_3d_signed(-1, false).unwrap().speed(), 1);
+        assert!(Frame::new_3d_signed(1001, false).is_none());
+        assert!(Frame::new_3d_signed(i16::MIN, false).is_none());
+        assert_eq!(
+            Frame::new_3d_signed(1000, false).unwrap().throttle_field(),
+            2047
+        );
+        assert_eq!(
+            Frame::new_3d_signed(-1000, false).unwrap().throttle_field(),
+            1047
+        );
+    }
+
+    #[test]
+    fn frame_3d_maps_into_halves() {
+        let reverse = Frame::new_3d(Direction3D::Reverse, 999, false).unwrap();
+        assert_eq!(reverse.throttle_field(), 1047);
+        let forward = Frame::new_3d(Direction3D::Forward, 0, false).unwrap();
+        assert_eq!(forward.throttle_field(), 1048);
+        assert!(Frame::new_3d(Direction3D::Reverse, 1000, false).is_none());
+    }
+
+    #[test]
+    fn set_speed_keeps_telemetry_and_bidirectional_crc() {
+        let mut frame = Frame::new_bidirectional(998, true).unwrap();
+        frame.set_speed(50).unwrap();
+        assert_eq!(frame, Frame::new_bidirectional(50, true).unwrap());
+        assert_eq!(
+            frame.set_speed(2000),
+            Err(FrameError::SpeedOutOfRange(2000))
+        );
+        assert_eq!(frame.speed(), 50);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize_rejects_invalid_crc() {
+        use serde::de::value::{Error, U16Deserializer};
+        use serde::Deserialize;
+
+        let deserialize = |value| Frame::deserialize(U16Deserializer::<Error>::new(value));
+        let frame = Frame::new(998, true).unwrap();
+        assert_eq!(deserialize(frame.inner()), Ok(frame));
+        let frame = Frame::new_bidirectional(998, true).unwrap();
+        assert_eq!(deserialize(frame.inner()), Ok(frame));
+        assert!(deserialize(frame.with_bit_flipped(0).inner()).is_err());
+    }
+
+    #[test]
+    fn repeat_frame_rejects_overflowing_count() {
+        let mut buffer = [0; 17];
+        assert_eq!(
+            repeat_frame(&Frame::idle(), usize::MAX / 2, MAX_DUTY_CYCLE, &mut buffer),
+            Err(BufferError {
+                required: usize::MAX,
+                actual: 17
+            })
+        );
+    }
+
+    #[test]
+    fn set_speed_keeps_corrupt_crc() {
+        let mut frame = Frame::new(998, true).unwrap().with_bit_flipped(15);
+        frame.set_speed(100).unwrap();
+        assert_eq!(frame.speed(), 100);
+        assert_eq!(detect_crc_variant(frame.inner()), None);
+        assert_eq!(frame.with_bit_flipped(15), Frame::new(100, true).unwrap());
+
+        let mut frame = Frame::new_bidirectional(998, true).unwrap();
+        frame.set_speed(100).unwrap();
+        assert_eq!(
+            detect_crc_variant(frame.inner()),
+            Some(CrcVariant::Inverted)
+        );
+    }
+
+    #[test]
+    fn with_telemetry_keeps_corrupt_crc() {
+        let corrupt = Frame::new(998, false).unwrap().with_bit_flipped(15);
+        let frame = corrupt.with_telemetry(true);
+        assert!(frame.telemetry_enabled());
+        assert_eq!(detect_crc_variant(frame.inner()), None);
+        assert_eq!(frame.with_telemetry(false), corrupt);
+    }
+
+    #[test]
+    fn expected_crc_ignores_stored_crc() {
+        let frame = Frame::new_bidirectional(998, true).unwrap();
+        assert_eq!(frame.expected_crc(), 0x07);
+        assert_eq!(frame.crc(), 0x08);
+    }
+
+    #[test]
+    fn new_with_standard_checksum_matches_new() {
+        assert_eq!(
+            Frame::new_with_checksum(998, true, CrcVariant::standard()),
+            Frame::new(998, true)
+        );
+        assert_eq!(
+            Frame::new_with_checksum(2000, true, CrcVariant::Normal),
+            None
+        );
+        let frame = Frame::new_with_checksum(998, true, |data: u16| data).unwrap();
+        assert_eq!(
+            frame.crc(),
+            (Frame::new(998, true).unwrap().inner() >> 4) & 0x0F
+        );
+    }
+
+    #[test]
+    fn crc_covers_telemetry_bit() {
+        assert_ne!(
+            Frame::new(1000, true).unwrap().crc(),
+            Frame::new(1000, false).unwrap().crc()
+        );
+    }
+
+    #[test]
+    fn with_telemetry_recomputes_crc() {
+        let frame = Frame::new(998, false).unwrap().with_telemetry(true);
+        assert!(frame.telemetry_enabled());
+        assert_eq!(frame.crc(), 0x07);
+        assert_eq!(frame.with_telemetry(false).crc(), 0x06);
+
+        let frame = Frame::new_bidirectional(998, false).unwrap();
+        assert_eq!(
+            frame.with_telemetry(true),
+            Frame::new_bidirectional(998, true).unwrap()
+        );
+    }
+
+    #[test]
+    fn throttle_percent_covers_full_range() {
+        assert_eq!(Frame::new(0, false).unwrap().throttle_percent(), Some(0));
+        assert_eq!(
+            Frame::new(1998, false).unwrap().throttle_percent(),
+            Some(99)
+        );
+        assert_eq!(Frame::from_raw_unchecked(0x01EF).throttle_percent(), None);
+    }
+
+    #[test]
+    fn throttle_from_permille_rounds_to_nearest() {
+        assert_eq!(throttle_from_permille(0), Some(0));
+        assert_eq!(throttle_from_permille(1), Some(2));
+        assert_eq!(throttle_from_permille(999), Some(1997));
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn throttle_from_fraction_validates_input() {
+        assert_eq!(throttle_from_fraction(0.0), Some(0));
+        assert_eq!(throttle_from_fraction(-0.1), None);
+        assert_eq!(throttle_from_fraction(f32::NAN), None);
+    }
+
+    #[test]
+    fn command_try_from_rejects_reserved_values() {
+        assert_eq!(Command::try_from(0), Ok(Command::MotorStop));
+        assert_eq!(Command::try_from(36), Err(FrameError::InvalidCommand(36)));
+        assert_eq!(Command::try_from(48), Err(FrameError::InvalidCommand(48)));
+    }
+
+    #[test]
+    fn compute_crc_overwrites_dirty_checksum() {
+        let clean = Frame::new(998, false).unwrap();
+        for dirty in 0..0x10 {
+            let mut frame = Frame {
+                inner: (clean.inner() & !0x0F) | dirty,
+            };
+            frame.compute_crc();
+            assert_eq!(frame, clean);
+        }
+    }
+
+    #[test]
+    fn from_duty_cycles_tolerates_deviations() {
+        let frame = Frame::new(999, true).unwrap();
+        let mut duty_cycles = frame.duty_cycles_no_reset(MAX_DUTY_CYCLE);
+        for duty_cycle in duty_cycles.iter_mut() {
+            *duty_cycle = if *duty_cycle == ONE { 60 } else { 50 };
+        }
+        assert_eq!(
+            Frame::from_duty_cycles(&duty_cycles, MAX_DUTY_CYCLE),
+            Ok(frame)
+        );
+    }
+
+    #[test]
+    fn from_duty_cycles_reports_errors() {
+        let frame = Frame::new(999, false).unwrap();
+        let mut duty_cycles = frame.duty_cycles(MAX_DUTY_CYCLE);
+        assert_eq!(
+            Frame::from_duty_cycles(&duty_cycles[..15], MAX_DUTY_CYCLE),
+            Err(DecodeError::BufferTooShort(BufferError {
+                required: 16,
+                actual: 15
+            }))
+        );
+        duty_cycles[15] = ONE;
+        assert!(matches!(
+            Frame::from_duty_cycles(&duty_cycles, MAX_DUTY_CYCLE),
+            Err(DecodeError::InvalidCrc(_))
+        ));
+    }
+
+    #[test]
+    fn from_raw_accepts_valid_frames() {
+        let frame = Frame::new(998, true).unwrap();
+        let parsed = Frame::from_raw(frame.inner()).unwrap();
+        assert_eq!(parsed, frame);
+    }
+
+    #[test]
+    fn from_raw_round_trips_all_frames() {
+        for request_telemetry in [false, true] {
+            for speed in 0..2000 {
+                let frame = Frame::new(speed, request_telemetry).unwrap();
+                let parsed = Frame::from_raw(frame.inner());
+                assert_eq!(parsed, Ok(frame));
+                let parsed = parsed.unwrap();
+                assert_eq!(parsed.speed(), speed);
+                assert_eq!(parsed.telemetry_enabled(), request_telemetry);
+                assert_eq!(parsed.crc(), frame.crc());
+            }
+            for value in 0..48 {
+                if let Some(command) = Command::from_u16(value) {
+                    let frame = Frame::command(command, request_telemetry);
+                    assert_eq!(Frame::from_raw(frame.inner()), Ok(frame));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_raw_unchecked_keeps_invalid_crc() {
+        let frame = Frame::from_raw_unchecked(0xFFF1);
+        assert_eq!(frame.inner(), 0xFFF1);
+        assert_eq!(frame.crc(), 0x01);
+        assert!(!frame.checksum_valid());
+    }
+
+    #[test]
+    fn every_flipped_bit_invalidates_crc() {
+        let frame = Frame::new(998, true).unwrap();
+        for index in 0..FRAME_BITS as u8 {
+            assert!(!frame.with_bit_flipped(index).checksum_valid());
+        }
+    }
+
+    #[test]
+    fn from_raw_rejects_invalid_crc() {
+        let frame = Frame::new(998, false).unwrap();
+        assert_eq!(
+            Frame::from_raw((frame.inner() & !0x0F) | 0x05).unwrap_err(),
+            CrcError {
+                expected: 0x06,
+                actual: 0x05
+            }
+        );
+    }
 }
@@ -0,0 +1,360 @@
+//! Sequences of frames that need to be sent in a specific order and timing.
+
+use crate::{Command, Frame, FrameError};
+
+/// The number of motor stop frames recommended to arm an ESC, which is what [`ArmingSequence`]
+/// sends by default.
+pub const ARMING_FRAMES_RECOMMENDED: u16 = 500;
+
+/// A sequence of frames that arms an ESC.
+///
+/// ESCs only arm after receiving motor stop frames for a while, after which they accept throttle
+/// frames. This iterates over the motor stop frames, each paired with the time in microseconds to
+/// wait before sending the next frame. Once done, keep the ESC armed by sending throttle frames,
+/// such as [`Frame::idle`].
+///
+/// By default, this sends [`ARMING_FRAMES_RECOMMENDED`] frames, regardless of the interval
+/// between them. Setting a duration sends as many frames as fit into it instead.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let sequence = ArmingSequence::new().duration_us(10_000).interval_us(1_000);
+/// assert_eq!(sequence.count(), 10);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ArmingSequence {
+    frame: Frame,
+    duration_us: Option<u32>,
+    interval_us: u32,
+    sent: u32,
+}
+
+impl ArmingSequence {
+    /// Creates a new sequence sending [`ARMING_FRAMES_RECOMMENDED`] motor stop frames, one every
+    /// millisecond.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            frame: Frame::command(Command::MotorStop, false),
+            duration_us: None,
+            interval_us: 1_000,
+            sent: 0,
+        }
+    }
+
+    /// Sets for how long in microseconds to send motor stop frames, instead of sending a fixed
+    /// number of frames.
+    #[must_use]
+    pub const fn duration_us(mut self, duration_us: u32) -> Self {
+        self.duration_us = Some(duration_us);
+        self
+    }
+
+    /// Sets the time in microseconds between two frames.
+    ///
+    /// An interval of zero is treated as one microsecond.
+    #[must_use]
+    pub const fn interval_us(mut self, interval_us: u32) -> Self {
+        self.interval_us = if interval_us == 0 { 1 } else { interval_us };
+        self
+    }
+
+    /// Sets whether to send bidirectional frames.
+    #[must_use]
+    pub const fn bidirectional(mut self, bidirectional: bool) -> Self {
+        let request_telemetry = self.frame.telemetry_enabled();
+        self.frame = if bidirectional {
+            Frame::command_bidirectional(Command::MotorStop, request_telemetry)
+        } else {
+            Frame::command(Command::MotorStop, request_telemetry)
+        };
+        self
+    }
+
+    /// Returns the total number of frames in the sequence.
+    const fn total(&self) -> u32 {
+        match self.duration_us {
+            Some(duration_us) => duration_us.div_ceil(self.interval_us),
+            None => ARMING_FRAMES_RECOMMENDED as u32,
+        }
+    }
+}
+
+impl Default for ArmingSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for ArmingSequence {
+    type Item = (Frame, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sent >= self.total() {
+            return None;
+        }
+        self.sent += 1;
+        Some((self.frame, self.interval_us))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total().saturating_sub(self.sent) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ArmingSequence {}
+
+/// Returns an endless stream of [`Frame::idle`] frames, which keep an armed ESC armed without
+/// spinning the motor.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let mut frames = ArmingSequence::new()
+///     .map(|(frame, _)| frame)
+///     .chain(idle_stream());
+/// assert_eq!(frames.nth(500), Some(Frame::idle()));
+/// ```
+pub fn idle_stream() -> impl Iterator<Item = Frame> + Clone {
+    core::iter::repeat(Frame::idle())
+}
+
+/// The time in microseconds to wait between two frames of a command sequence.
+const COMMAND_INTERVAL_US: u32 = 1_000;
+
+/// Returns the frames that turn 3D mode on or off and save the setting, each paired with the time
+/// in microseconds to wait before sending the next frame.
+///
+/// Each command is repeated as often as [`Command::required_repetitions`] demands, and its last
+/// frame is followed by at least [`Command::min_delay_after_us`]. The motors need to be stopped
+/// while doing this.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let mut sequence = configure_3d_mode(true);
+/// assert_eq!(sequence.next(), Some((Frame::from(Command::ThreeDModeOn), 1_000)));
+/// assert_eq!(sequence.last(), Some((Frame::from(Command::SettingsSave), 35_000)));
+/// ```
+pub fn configure_3d_mode(enable: bool) -> impl Iterator<Item = (Frame, u32)> + Clone {
+    let command = if enable {
+        Command::ThreeDModeOn
+    } else {
+        Command::ThreeDModeOff
+    };
+    command_sequence(command).chain(command_sequence(Command::SettingsSave))
+}
+
+/// Returns the frames needed for the ESC to act upon the given command, each paired with the time
+/// in microseconds to wait before sending the next frame.
+fn command_sequence(command: Command) -> impl Iterator<Item = (Frame, u32)> + Clone {
+    let repetitions = command.required_repetitions();
+    let delay_us = match command.min_delay_after_us() {
+        Some(delay_us) if delay_us > COMMAND_INTERVAL_US => delay_us,
+        _ => COMMAND_INTERVAL_US,
+    };
+    (1..=repetitions).map(move |repetition| {
+        let wait_us = if repetition == repetitions {
+            delay_us
+        } else {
+            COMMAND_INTERVAL_US
+        };
+        (Frame::from(command), wait_us)
+    })
+}
+
+/// Requests telemetry on one out of every `period` frames, to avoid overloading the telemetry
+/// channel.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let mut scheduler = TelemetryScheduler::new(3);
+/// let frames = [(); 4].map(|_| scheduler.next(1000).unwrap());
+/// assert_eq!(frames.map(|frame| frame.telemetry_enabled()), [true, false, false, true]);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TelemetryScheduler {
+    period: u16,
+    counter: u16,
+}
+
+impl TelemetryScheduler {
+    /// Creates a new scheduler requesting telemetry on the next frame and every `period` frames
+    /// after that.
+    ///
+    /// A period of zero never requests telemetry.
+    #[must_use]
+    pub const fn new(period: u16) -> Self {
+        Self { period, counter: 0 }
+    }
+
+    /// Creates the next frame with the given speed (0-1999), requesting telemetry if it is due.
+    ///
+    /// Returns [`None`] if the speed is out of bounds, which still counts as a frame.
+    pub fn next(&mut self, speed: u16) -> Option<Frame> {
+        let request_telemetry = self.period != 0 && self.counter == 0;
+        if self.period != 0 {
+            self.counter = (self.counter + 1) % self.period;
+        }
+        Frame::new(speed, request_telemetry)
+    }
+}
+
+/// A linear ramp from one speed to another over a number of frames, using integer math only.
+///
+/// The `i`-th of the `steps` frames has the speed `start + (end - start) * i / steps`, rounded
+/// towards the start speed, so the last frame has the end speed. If `steps` exceeds the difference
+/// between the speeds, some speeds are repeated, including the start speed.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let ramp = ThrottleRamp::new(0, 1000, 4).unwrap();
+/// assert!(ramp.map(|frame| frame.speed()).eq([250, 500, 750, 1000]));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ThrottleRamp {
+    start: u16,
+    end: u16,
+    steps: u16,
+    sent: u16,
+}
+
+impl ThrottleRamp {
+    /// Creates a new ramp from the `start` speed to the `end` speed (0-1999) in `steps` frames.
+    ///
+    /// Returns [`FrameError::SpeedOutOfRange`] if either speed is out of bounds.
+    pub const fn new(start: u16, end: u16, steps: u16) -> Result<Self, FrameError> {
+        if let Err(error) = Frame::try_new(start, false) {
+            return Err(error);
+        }
+        if let Err(error) = Frame::try_new(end, false) {
+            return Err(error);
+        }
+        Ok(Self {
+            start,
+            end,
+            steps,
+            sent: 0,
+        })
+    }
+}
+
+impl Iterator for ThrottleRamp {
+    type Item = Frame;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sent >= self.steps {
+            return None;
+        }
+        self.sent += 1;
+        let delta = (self.end as i32 - self.start as i32) * self.sent as i32 / self.steps as i32;
+        Frame::new((self.start as i32 + delta) as u16, false)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.steps - self.sent) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ThrottleRamp {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arming_sequence_rounds_up() {
+        let sequence = ArmingSequence::new().duration_us(2_500).interval_us(1_000);
+        assert_eq!(sequence.len(), 3);
+    }
+
+    #[test]
+    fn arming_sequence_sends_recommended_frames_at_any_interval() {
+        let sequence = ArmingSequence::new().interval_us(500);
+        assert_eq!(sequence.len(), ARMING_FRAMES_RECOMMENDED as usize);
+    }
+
+    #[test]
+    fn arming_sequence_shortened_mid_iteration_is_empty() {
+        let mut sequence = ArmingSequence::new();
+        sequence.nth(9);
+        let sequence = sequence.duration_us(5_000);
+        assert_eq!(sequence.len(), 0);
+        assert_eq!(sequence.count(), 0);
+    }
+
+    #[test]
+    fn arming_sequence_sends_motor_stop() {
+        let mut sequence = ArmingSequence::new().bidirectional(true);
+        assert_eq!(
+            sequence.next(),
+            Some((
+                Frame::command_bidirectional(Command::MotorStop, false),
+                1_000
+            ))
+        );
+        assert_eq!(sequence.len(), 499);
+    }
+
+    #[test]
+    fn telemetry_scheduler_with_zero_period_never_requests() {
+        let mut scheduler = TelemetryScheduler::new(0);
+        assert!((0..10).all(|_| !scheduler.next(0).unwrap().telemetry_enabled()));
+    }
+
+    #[test]
+    fn telemetry_scheduler_with_unit_period_always_requests() {
+        let mut scheduler = TelemetryScheduler::new(1);
+        assert!((0..10).all(|_| scheduler.next(0).unwrap().telemetry_enabled()));
+    }
+
+    #[test]
+    fn configure_3d_mode_repeats_commands() {
+        let sequence = configure_3d_mode(false);
+        assert_eq!(sequence.clone().count(), 12);
+        assert!(sequence
+            .clone()
+            .take(6)
+            .all(|(frame, _)| frame == Frame::from(Command::ThreeDModeOff)));
+        assert!(sequence
+            .skip(6)
+            .all(|(frame, _)| frame == Frame::from(Command::SettingsSave)));
+    }
+
+    #[test]
+    fn command_sequence_waits_after_last_frame() {
+        let sequence = command_sequence(Command::Beep1);
+        assert!(sequence.eq([(Frame::from(Command::Beep1), 260_000)]));
+    }
+
+    #[test]
+    fn throttle_ramp_repeats_speeds_with_many_steps() {
+        let ramp = ThrottleRamp::new(0, 2, 4).unwrap();
+        assert!(ramp.map(|frame| frame.speed()).eq([0, 1, 1, 2]));
+        let ramp = ThrottleRamp::new(2, 0, 4).unwrap();
+        assert!(ramp.map(|frame| frame.speed()).eq([2, 1, 1, 0]));
+    }
+
+    #[test]
+    fn throttle_ramp_goes_down() {
+        let ramp = ThrottleRamp::new(1999, 0, 3).unwrap();
+        assert_eq!(ramp.len(), 3);
+        assert!(ramp.map(|frame| frame.speed()).eq([1333, 667, 0]));
+        assert_eq!(
+            ThrottleRamp::new(0, 2000, 3),
+            Err(FrameError::SpeedOutOfRange(2000))
+        );
+        assert_eq!(ThrottleRamp::new(0, 1000, 0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn idle_stream_is_endless() {
+        assert!(idle_stream()
+            .take(1_000)
+            .all(|frame| frame == Frame::idle()));
+        assert_eq!(idle_stream().size_hint(), (usize::MAX, None));
+    }
+}
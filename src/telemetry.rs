@@ -0,0 +1,686 @@
+//! Decoding of telemetry sent back by the ESC, and encoding it for testing.
+//!
+//! With bidirectional DShot the ESC answers every frame with an eRPM telemetry frame on the same
+//! signal line. It carries 12 bits of data and a four bit checksum, GCR-encoded into 20 bits,
+//! which are then transmitted as transitions (a one is a change in level, a zero is none) after
+//! a start bit, for a total of 21 bits.
+
+use core::fmt;
+
+use crate::{dshot_crc_inverted, Command};
+
+/// Maps each five bit GCR symbol to the nibble it encodes, or `0xFF` for invalid symbols.
+const GCR_DECODE: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x09, 0x0A, 0x0B, 0xFF, 0x0D, 0x0E, 0x0F,
+    0xFF, 0xFF, 0x02, 0x03, 0xFF, 0x05, 0x06, 0x07, 0xFF, 0x00, 0x08, 0x01, 0xFF, 0x04, 0x0C, 0xFF,
+];
+
+/// Maps each nibble to the five bit GCR symbol encoding it.
+const GCR_ENCODE: [u8; 16] = [
+    0x19, 0x1B, 0x12, 0x13, 0x1D, 0x15, 0x16, 0x17, 0x1A, 0x09, 0x0A, 0x0B, 0x1E, 0x0D, 0x0E, 0x0F,
+];
+
+/// The eRPM period the ESC reports while the motor is stopped.
+///
+/// This is the largest period that can be encoded, all exponent and mantissa bits set.
+pub const ERPM_PERIOD_STOPPED: u16 = 0x1FF << 7;
+
+/// Errors that can occur while decoding telemetry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum TelemetryError {
+    /// The frame contains a five bit group that is not a valid GCR symbol.
+    InvalidSymbol,
+    /// The checksum does not match the data.
+    InvalidCrc,
+}
+
+impl fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSymbol => f.write_str("invalid GCR symbol"),
+            Self::InvalidCrc => f.write_str("invalid checksum"),
+        }
+    }
+}
+
+impl core::error::Error for TelemetryError {}
+
+/// Decodes a 21-bit bidirectional DShot response into its 12 bits of data.
+///
+/// The start bit is expected in bit 20, followed by the remaining bits MSB-first. The checksum of
+/// the response is validated, which for bidirectional DShot is inverted.
+pub fn decode_response(value: u32) -> Result<u16, TelemetryError> {
+    let value = value & 0x1F_FFFF;
+    let gcr = (value ^ (value >> 1)) & 0xF_FFFF;
+
+    let mut decoded: u16 = 0;
+    for shift in [15, 10, 5, 0] {
+        let nibble = GCR_DECODE[((gcr >> shift) & 0x1F) as usize];
+        if nibble == 0xFF {
+            return Err(TelemetryError::InvalidSymbol);
+        }
+        decoded = (decoded << 4) | nibble as u16;
+    }
+
+    let data = decoded >> 4;
+    if dshot_crc_inverted(data) != decoded & 0x0F {
+        return Err(TelemetryError::InvalidCrc);
+    }
+    Ok(data)
+}
+
+/// Returns whether a 21-bit bidirectional DShot response has a valid checksum.
+///
+/// Responses that cannot be GCR-decoded are not valid either. See [`decode_response`] for the
+/// expected input format.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert!(response_crc_valid(0xED525));
+/// assert!(!response_crc_valid(0xED524));
+/// ```
+#[must_use]
+pub fn response_crc_valid(value: u32) -> bool {
+    decode_response(value).is_ok()
+}
+
+/// Encodes 12 bits of data into a 21-bit bidirectional DShot response, as an ESC would send it.
+///
+/// This is the inverse of [`decode_response`], with the start bit low.
+#[must_use]
+pub const fn encode_response(data: u16) -> u32 {
+    let data = data & 0x0FFF;
+    let value = (data << 4) | dshot_crc_inverted(data);
+
+    let mut gcr: u32 = 0;
+    let mut shift = 16;
+    while shift > 0 {
+        shift -= 4;
+        gcr = (gcr << 5) | GCR_ENCODE[((value >> shift) & 0x0F) as usize] as u32;
+    }
+
+    let mut rv: u32 = 0;
+    let mut level = 0;
+    let mut bit = 20;
+    while bit > 0 {
+        bit -= 1;
+        level ^= (gcr >> bit) & 1;
+        rv |= level << bit;
+    }
+    rv
+}
+
+/// Encodes an eRPM period in microseconds into a 21-bit bidirectional DShot response, as an ESC
+/// would send it.
+///
+/// This is the inverse of [`decode_erpm`]. Periods that do not fit into the nine bit mantissa
+/// lose their lowest bits, like they do when sent by an ESC.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(encode_erpm_response(1000), 0xED525);
+/// assert_eq!(decode_erpm(encode_erpm_response(1000)), Ok(1000));
+/// ```
+#[must_use]
+pub const fn encode_erpm_response(period_us: u16) -> u32 {
+    let mut exponent = 0;
+    while period_us >> exponent > 0x1FF {
+        exponent += 1;
+    }
+    encode_response((exponent << 9) | (period_us >> exponent))
+}
+
+/// Decodes a 21-bit bidirectional DShot response into the eRPM period in microseconds.
+///
+/// The data is transmitted as a three bit exponent followed by a nine bit mantissa, which this
+/// expands into the full period. See [`decode_response`] for the expected input format.
+///
+/// With extended DShot telemetry enabled, use [`decode_response`] and [`parse_edt`] instead, as
+/// some responses carry telemetry values rather than the eRPM period.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(decode_erpm(0xED525), Ok(1000));
+/// assert_eq!(decode_erpm(0xED524), Err(TelemetryError::InvalidCrc));
+/// ```
+pub fn decode_erpm(value: u32) -> Result<u16, TelemetryError> {
+    let data = decode_response(value)?;
+    Ok((data & 0x1FF) << (data >> 9))
+}
+
+/// Converts an eRPM period in microseconds into the mechanical RPM of a motor with the given
+/// number of poles.
+///
+/// The period covers one electrical revolution, so it is first turned into eRPM, which is then
+/// divided by the number of pole pairs. A period of zero or [`ERPM_PERIOD_STOPPED`] means the
+/// motor is stopped and returns zero. Motors with fewer than two poles are treated as having
+/// one pole pair.
+///
+/// ```
+/// # use dshot_frame::*;
+/// // 1000us per electrical revolution is 60000 eRPM, or 8571 RPM on a 14 pole motor.
+/// assert_eq!(erpm_period_to_rpm(1000, 14), 8571);
+/// // 250us is 240000 eRPM, or 20000 RPM on a 24 pole motor.
+/// assert_eq!(erpm_period_to_rpm(250, 24), 20000);
+/// assert_eq!(erpm_period_to_rpm(ERPM_PERIOD_STOPPED, 14), 0);
+/// ```
+#[must_use]
+pub fn erpm_period_to_rpm(period_us: u16, motor_poles: u8) -> u32 {
+    if period_us == 0 || period_us >= ERPM_PERIOD_STOPPED {
+        return 0;
+    }
+    let pole_pairs = (motor_poles / 2).max(1) as u32;
+    60_000_000 / period_us as u32 / pole_pairs
+}
+
+/// An eRPM period in microseconds, as sent by the ESC.
+///
+/// This is the time one electrical revolution takes, which needs the number of motor poles to be
+/// turned into an [`Rpm`]. It converts into an [`ErpmPeriodResult`] with the stopped case
+/// separated out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct ErpmPeriod(pub u16);
+
+impl ErpmPeriod {
+    /// Decodes a 21-bit bidirectional DShot response, see [`decode_erpm`].
+    ///
+    /// ```
+    /// # use dshot_frame::*;
+    /// let period = ErpmPeriod::decode(0xED525).unwrap();
+    /// assert_eq!(period.to_rpm(14), Rpm(8571));
+    /// ```
+    pub fn decode(value: u32) -> Result<Self, TelemetryError> {
+        decode_erpm(value).map(Self)
+    }
+
+    /// Returns whether the period means the motor is stopped.
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        self.0 == 0 || self.0 >= ERPM_PERIOD_STOPPED
+    }
+
+    /// Converts the period into the mechanical RPM of a motor with the given number of poles, see
+    /// [`erpm_period_to_rpm`].
+    #[must_use]
+    pub fn to_rpm(&self, motor_poles: u8) -> Rpm {
+        Rpm(erpm_period_to_rpm(self.0, motor_poles))
+    }
+}
+
+/// Mechanical revolutions per minute of a motor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct Rpm(pub u32);
+
+/// The number of bits in a bidirectional DShot response, including the start bit.
+const RESPONSE_BITS: u8 = 21;
+
+/// A decoder for the eRPM responses an ESC streams after
+/// [`Command::SignalLineContinuousERPMTelemetry`](crate::Command::SignalLineContinuousERPMTelemetry).
+///
+/// The line is sampled once per bit and fed into the decoder, which waits for the low start bit
+/// of a response, collects its 21 bits and decodes them with [`decode_erpm`]. The line idles high
+/// between responses, so any number of high bits in between are skipped. After an invalid
+/// response, the decoder waits for the next start bit again.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let response = encode_erpm_response(1000);
+/// let mut decoder = ContinuousErpmDecoder::new();
+/// assert_eq!(decoder.push_bit(true), None);
+/// let mut decoded = None;
+/// for bit in (0..21).rev() {
+///     decoded = decoder.push_bit((response >> bit) & 1 != 0);
+/// }
+/// assert_eq!(decoded, Some(Ok(ErpmPeriod(1000))));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ContinuousErpmDecoder {
+    value: u32,
+    bits: u8,
+}
+
+impl ContinuousErpmDecoder {
+    /// Creates a new decoder waiting for a start bit.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { value: 0, bits: 0 }
+    }
+
+    /// Feeds the next sampled line level into the decoder.
+    ///
+    /// Returns the decoded eRPM period once a full response has arrived.
+    pub fn push_bit(&mut self, level: bool) -> Option<Result<ErpmPeriod, TelemetryError>> {
+        if self.bits == 0 && level {
+            return None;
+        }
+        self.value = (self.value << 1) | level as u32;
+        self.bits += 1;
+        if self.bits < RESPONSE_BITS {
+            return None;
+        }
+        let value = self.value;
+        self.reset();
+        Some(ErpmPeriod::decode(value))
+    }
+
+    /// Feeds eight sampled line levels into the decoder, MSB first.
+    ///
+    /// A response is longer than a byte, so at most one response completes per byte.
+    pub fn push_byte(&mut self, byte: u8) -> Option<Result<ErpmPeriod, TelemetryError>> {
+        let mut rv = None;
+        for shift in (0..8).rev() {
+            if let Some(decoded) = self.push_bit((byte >> shift) & 1 != 0) {
+                rv = Some(decoded);
+            }
+        }
+        rv
+    }
+
+    /// Discards a partially received response and waits for the next start bit.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// A telemetry value, scaled to its unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Telemetry {
+    /// Temperature in ºC, saturating at 255ºC.
+    Temperature(u8),
+    /// Voltage in mV.
+    Voltage(u16),
+    /// Current in mA.
+    Current(u32),
+    /// Consumption in mAh.
+    Consumption(u16),
+    /// Electrical revolutions per minute.
+    Erpm(u32),
+    /// eRPM period, see [`decode_erpm_period`].
+    ErpmPeriod(ErpmPeriodResult),
+}
+
+/// A value sent with extended DShot telemetry (EDT), scaled to its unit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdtTelemetry {
+    /// Temperature in ºC.
+    Temperature(u8),
+    /// Voltage in mV.
+    Voltage(u16),
+    /// Current in mA.
+    Current(u32),
+    Debug1(u8),
+    Debug2(u8),
+    /// Stress level, higher is worse.
+    StressLevel(u8),
+    /// Status flags.
+    Status(u8),
+}
+
+/// The kind of a bidirectional response with extended DShot telemetry (EDT) enabled, see
+/// [`edt_frame_kind`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdtFrameKind {
+    /// An eRPM period, see [`decode_erpm`].
+    Erpm,
+    Temperature,
+    Voltage,
+    Current,
+    Debug1,
+    Debug2,
+    StressLevel,
+    Status,
+}
+
+/// Classifies the 12 bits of data of a bidirectional response, as returned by
+/// [`decode_response`], with extended DShot telemetry enabled.
+///
+/// Telemetry values have an even, non-zero type in the upper four bits, everything else is an
+/// eRPM period.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(edt_frame_kind(0x232), EdtFrameKind::Temperature);
+/// assert_eq!(edt_frame_kind(0x1F4), EdtFrameKind::Erpm);
+/// ```
+#[must_use]
+pub const fn edt_frame_kind(raw: u16) -> EdtFrameKind {
+    match (raw >> 8) & 0x0F {
+        0x02 => EdtFrameKind::Temperature,
+        0x04 => EdtFrameKind::Voltage,
+        0x06 => EdtFrameKind::Current,
+        0x08 => EdtFrameKind::Debug1,
+        0x0A => EdtFrameKind::Debug2,
+        0x0C => EdtFrameKind::StressLevel,
+        0x0E => EdtFrameKind::Status,
+        _ => EdtFrameKind::Erpm,
+    }
+}
+
+/// Parses the 12 bits of data of a bidirectional response, as returned by [`decode_response`],
+/// as extended DShot telemetry.
+///
+/// Once enabled with [`Command::ExtendedTelemetryEnable`], the ESC interleaves eRPM responses
+/// with telemetry values. Those have an even, non-zero type in the upper four bits, followed by
+/// eight bits of value. Returns [`None`] for eRPM responses.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(parse_edt(0x232), Some(EdtTelemetry::Temperature(50)));
+/// assert_eq!(parse_edt(0x4A8), Some(EdtTelemetry::Voltage(42_000)));
+/// assert_eq!(parse_edt(0x1F4), None);
+/// ```
+#[must_use]
+pub fn parse_edt(raw: u16) -> Option<EdtTelemetry> {
+    let value = raw as u8;
+    let telemetry = match edt_frame_kind(raw) {
+        EdtFrameKind::Erpm => return None,
+        EdtFrameKind::Temperature => EdtTelemetry::Temperature(value),
+        EdtFrameKind::Voltage => EdtTelemetry::Voltage(value as u16 * 250),
+        EdtFrameKind::Current => EdtTelemetry::Current(value as u32 * 1000),
+        EdtFrameKind::Debug1 => EdtTelemetry::Debug1(value),
+        EdtFrameKind::Debug2 => EdtTelemetry::Debug2(value),
+        EdtFrameKind::StressLevel => EdtTelemetry::StressLevel(value),
+        EdtFrameKind::Status => EdtTelemetry::Status(value),
+    };
+    Some(telemetry)
+}
+
+/// Telemetry sent by the ESC over a separate UART wire, in the KISS/BLHeli format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KissTelemetry {
+    /// Temperature in ºC.
+    pub temperature: u8,
+    /// Voltage in mV.
+    pub voltage: u32,
+    /// Current in mA.
+    pub current: u32,
+    /// Consumption in mAh.
+    pub consumption: u16,
+    /// Electrical revolutions per minute.
+    pub erpm: u32,
+}
+
+/// Parses a ten byte KISS/BLHeli UART telemetry frame, validating its checksum.
+///
+/// The frame contains the temperature (1ºC per LSB), voltage (10mV per LSB), current (10mA per
+/// LSB), consumption (1mAh per LSB) and eRPM (100eRPM per LSB), all big-endian, followed by a
+/// CRC8 over the first nine bytes.
+///
+/// ```
+/// # use dshot_frame::*;
+/// let bytes = [40, 0x06, 0x90, 0x03, 0xE8, 0x00, 0x64, 0x01, 0xF4, 0x1F];
+/// let telemetry = parse_kiss_telemetry(&bytes).unwrap();
+/// assert_eq!(telemetry.voltage, 16_800);
+/// assert_eq!(telemetry.erpm, 50_000);
+/// ```
+pub fn parse_kiss_telemetry(bytes: &[u8; 10]) -> Result<KissTelemetry, TelemetryError> {
+    if kiss_crc8(&bytes[..9]) != bytes[9] {
+        return Err(TelemetryError::InvalidCrc);
+    }
+    let field = |index: usize| u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+    Ok(KissTelemetry {
+        temperature: bytes[0],
+        voltage: field(1) as u32 * 10,
+        current: field(3) as u32 * 10,
+        consumption: field(5),
+        erpm: field(7) as u32 * 100,
+    })
+}
+
+/// Computes the CRC8 (polynomial 0x07) used by KISS/BLHeli UART telemetry.
+fn kiss_crc8(bytes: &[u8]) -> u8 {
+    let mut crc: u8 = 0;
+    for byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Parses the 12 bits of data sent in response to one of the signal line telemetry commands.
+///
+/// Returns [`None`] if the command does not request a telemetry value, e.g.
+/// [`Command::SignalLineTelemetryEnable`].
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(
+///     parse_telemetry(Command::SignalLineVoltageTelemetry, 1680),
+///     Some(Telemetry::Voltage(16_800))
+/// );
+/// assert_eq!(parse_telemetry(Command::Beep1, 1680), None);
+/// ```
+#[must_use]
+pub fn parse_telemetry(command: Command, raw: u16) -> Option<Telemetry> {
+    let raw = raw & 0x0FFF;
+    let telemetry = match command {
+        Command::SignalLineTemperatureTelemetry => {
+            Telemetry::Temperature(raw.min(u8::MAX as u16) as u8)
+        }
+        Command::SignalLineVoltageTelemetry => Telemetry::Voltage(raw * 10),
+        Command::SignalLineCurrentTelemetry => Telemetry::Current(raw as u32 * 100),
+        Command::SignalLineConsumptionTelemetry => Telemetry::Consumption(raw * 10),
+        Command::SignalLineERPMTelemetry => Telemetry::Erpm(raw as u32 * 100),
+        Command::SignalLineERPMPeriodTelemetry => Telemetry::ErpmPeriod(decode_erpm_period(raw)),
+        _ => return None,
+    };
+    Some(telemetry)
+}
+
+/// An eRPM period sent in response to [`Command::SignalLineERPMPeriodTelemetry`], see
+/// [`decode_erpm_period`].
+///
+/// Unlike [`ErpmPeriod`], which is the raw period of a bidirectional response that is stopped at
+/// [`ERPM_PERIOD_STOPPED`], this has the stopped case decoded already. Bidirectional periods can
+/// be converted into it as well.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(ErpmPeriodResult::from(ErpmPeriod(1000)), ErpmPeriodResult::Period(1000));
+/// assert_eq!(
+///     ErpmPeriodResult::from(ErpmPeriod(ERPM_PERIOD_STOPPED)),
+///     ErpmPeriodResult::Stopped
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErpmPeriodResult {
+    /// The motor is not rotating.
+    Stopped,
+    /// The eRPM period in microseconds.
+    Period(u32),
+}
+
+impl From<ErpmPeriod> for ErpmPeriodResult {
+    fn from(period: ErpmPeriod) -> Self {
+        if period.is_stopped() {
+            Self::Stopped
+        } else {
+            Self::Period(period.0 as u32)
+        }
+    }
+}
+
+/// Decodes the 12 bits of data sent in response to [`Command::SignalLineERPMPeriodTelemetry`].
+///
+/// The period is sent in steps of 16µs, and the largest value of 65520µs means the motor is
+/// stopped.
+///
+/// ```
+/// # use dshot_frame::*;
+/// assert_eq!(decode_erpm_period(100), ErpmPeriodResult::Period(1600));
+/// assert_eq!(decode_erpm_period(0x0FFF), ErpmPeriodResult::Stopped);
+/// ```
+#[must_use]
+pub const fn decode_erpm_period(raw: u16) -> ErpmPeriodResult {
+    let raw = raw & 0x0FFF;
+    if raw == 0x0FFF {
+        return ErpmPeriodResult::Stopped;
+    }
+    ErpmPeriodResult::Period(raw as u32 * 16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_erpm_works() {
+        assert_eq!(decode_erpm(0xED525), Ok(1000));
+    }
+
+    #[test]
+    fn edt_frame_kind_treats_odd_and_zero_types_as_erpm() {
+        assert_eq!(edt_frame_kind(0x0FF), EdtFrameKind::Erpm);
+        assert_eq!(edt_frame_kind(0x3FF), EdtFrameKind::Erpm);
+        assert_eq!(edt_frame_kind(0xE01), EdtFrameKind::Status);
+    }
+
+    #[test]
+    fn decode_erpm_period_ignores_upper_bits() {
+        assert_eq!(decode_erpm_period(0xF001), ErpmPeriodResult::Period(16));
+        assert_eq!(decode_erpm_period(0xFFFF), ErpmPeriodResult::Stopped);
+    }
+
+    #[test]
+    fn continuous_decoder_syncs_on_start_bits() {
+        // Two responses, separated by three idle bits and preceded by eleven, packed into bytes.
+        let stream: u64 = (0x7FF << 45)
+            | ((encode_erpm_response(1000) as u64) << 24)
+            | (0b111 << 21)
+            | encode_erpm_response(250) as u64;
+        let mut decoder = ContinuousErpmDecoder::new();
+        let decoded: [_; 7] =
+            core::array::from_fn(|index| decoder.push_byte((stream >> (48 - index * 8)) as u8));
+        assert_eq!(decoded[3], Some(Ok(ErpmPeriod(1000))));
+        assert_eq!(decoded[6], Some(Ok(ErpmPeriod(250))));
+        assert_eq!(decoded.iter().flatten().count(), 2);
+    }
+
+    #[test]
+    fn decode_erpm_ignores_line_polarity() {
+        assert_eq!(decode_erpm(!0xED525 & 0x1F_FFFF), Ok(1000));
+    }
+
+    #[test]
+    fn stopped_erpm_period_has_zero_rpm() {
+        let period = ErpmPeriod(ERPM_PERIOD_STOPPED);
+        assert!(period.is_stopped());
+        assert_eq!(period.to_rpm(14), Rpm(0));
+        assert!(!ErpmPeriod(1000).is_stopped());
+    }
+
+    #[test]
+    fn erpm_period_to_rpm_handles_stopped_motors() {
+        assert_eq!(erpm_period_to_rpm(0, 14), 0);
+        assert_eq!(erpm_period_to_rpm(ERPM_PERIOD_STOPPED, 14), 0);
+    }
+
+    #[test]
+    fn erpm_period_to_rpm_handles_degenerate_pole_counts() {
+        assert_eq!(erpm_period_to_rpm(1000, 0), 60_000);
+        assert_eq!(erpm_period_to_rpm(1000, 1), 60_000);
+    }
+
+    #[test]
+    fn parse_telemetry_scales_maximum_values() {
+        let cases = [
+            (
+                Command::SignalLineVoltageTelemetry,
+                Telemetry::Voltage(40_950),
+            ),
+            (
+                Command::SignalLineCurrentTelemetry,
+                Telemetry::Current(409_500),
+            ),
+            (
+                Command::SignalLineConsumptionTelemetry,
+                Telemetry::Consumption(40_950),
+            ),
+            (Command::SignalLineERPMTelemetry, Telemetry::Erpm(409_500)),
+            (
+                Command::SignalLineERPMPeriodTelemetry,
+                Telemetry::ErpmPeriod(ErpmPeriodResult::Stopped),
+            ),
+        ];
+        for (command, telemetry) in cases {
+            assert_eq!(parse_telemetry(command, 0xFFFF), Some(telemetry));
+        }
+    }
+
+    #[test]
+    fn parse_edt_ignores_erpm_responses() {
+        // Exponent 0 with any mantissa, and any exponent with the top mantissa bit set.
+        assert_eq!(parse_edt(0x0FF), None);
+        assert_eq!(parse_edt(0x3FF), None);
+        assert_eq!(parse_edt(0x632), Some(EdtTelemetry::Current(50_000)));
+        assert_eq!(parse_edt(0xE03), Some(EdtTelemetry::Status(3)));
+    }
+
+    #[test]
+    fn encode_response_round_trips() {
+        for data in 0..0x1000 {
+            assert_eq!(decode_response(encode_response(data)), Ok(data));
+        }
+    }
+
+    #[test]
+    fn encode_erpm_response_truncates_long_periods() {
+        assert_eq!(decode_erpm(encode_erpm_response(511)), Ok(511));
+        assert_eq!(decode_erpm(encode_erpm_response(513)), Ok(512));
+        assert_eq!(
+            decode_erpm(encode_erpm_response(u16::MAX)),
+            Ok(ERPM_PERIOD_STOPPED)
+        );
+    }
+
+    #[test]
+    fn parse_kiss_telemetry_rejects_invalid_crc() {
+        let bytes = [40, 0x06, 0x90, 0x03, 0xE8, 0x00, 0x64, 0x01, 0xF4, 0x00];
+        assert_eq!(
+            parse_kiss_telemetry(&bytes),
+            Err(TelemetryError::InvalidCrc)
+        );
+    }
+
+    #[test]
+    fn kiss_crc8_matches_reference() {
+        // CRC-8 with polynomial 0x07 of the ASCII digits 1-9.
+        assert_eq!(kiss_crc8(b"123456789"), 0xF4);
+    }
+
+    #[test]
+    fn decode_response_rejects_invalid_crc() {
+        // The same response with the checksum nibble changed from 0x7 (0x17) to 0x6 (0x16).
+        assert_eq!(decode_response(0xED524), Err(TelemetryError::InvalidCrc));
+    }
+
+    #[test]
+    fn decode_response_rejects_invalid_symbols() {
+        assert_eq!(decode_response(0), Err(TelemetryError::InvalidSymbol));
+    }
+}
@@ -0,0 +1,55 @@
+//! Integration with `ufmt`, mirroring the `core::fmt` implementations.
+
+use ufmt::{uDisplay, uWrite, uwrite, Formatter};
+
+use crate::{Frame, Payload};
+
+impl uDisplay for Frame {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        match self.payload() {
+            Payload::Throttle(speed) => uwrite!(f, "Frame(speed={}", speed)?,
+            Payload::Command(command) => uwrite!(f, "Frame(cmd={:?}", command)?,
+            Payload::Reserved(value) => uwrite!(f, "Frame(reserved={}", value)?,
+        }
+        uwrite!(
+            f,
+            ", telem={}, crc={:#x})",
+            self.telemetry_enabled(),
+            self.crc()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::String;
+
+    use ufmt::{uWrite, uwrite};
+
+    use crate::*;
+
+    struct Buffer(String);
+
+    impl uWrite for Buffer {
+        type Error = core::convert::Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn display_matches_core_fmt() {
+        for frame in [
+            Frame::new(998, false).unwrap(),
+            Frame::command(Command::MotorStop, true),
+        ] {
+            let mut buffer = Buffer(String::new());
+            uwrite!(buffer, "{}", frame).unwrap();
+            assert_eq!(buffer.0, std::format!("{frame}"));
+        }
+    }
+}
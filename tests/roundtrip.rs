@@ -0,0 +1,37 @@
+use dshot_frame::*;
+use proptest::prelude::*;
+
+fn command() -> impl Strategy<Value = Command> {
+    (0u16..48).prop_filter_map("reserved", Command::from_u16)
+}
+
+proptest! {
+    #[test]
+    fn from_raw_round_trips_throttle_frames(speed in 0u16..2000, request_telemetry: bool) {
+        let frame = Frame::new(speed, request_telemetry).unwrap();
+        prop_assert_eq!(Frame::from_raw(frame.inner()), Ok(frame));
+        prop_assert_eq!(frame.speed(), speed);
+        prop_assert_eq!(frame.telemetry_enabled(), request_telemetry);
+    }
+
+    #[test]
+    fn from_raw_round_trips_command_frames(command in command(), request_telemetry: bool) {
+        let frame = Frame::command(command, request_telemetry);
+        prop_assert_eq!(Frame::from_raw(frame.inner()), Ok(frame));
+        prop_assert_eq!(frame.payload(), Payload::Command(command));
+    }
+
+    #[test]
+    fn crc_always_validates(speed in 0u16..2000, request_telemetry: bool) {
+        let frame = Frame::new(speed, request_telemetry).unwrap();
+        prop_assert_eq!(frame.crc(), dshot_crc(frame.inner() >> 4));
+        let bidirectional = Frame::new_bidirectional(speed, request_telemetry).unwrap();
+        prop_assert_eq!(bidirectional.crc(), dshot_crc_inverted(bidirectional.inner() >> 4));
+    }
+
+    #[test]
+    fn from_raw_rejects_any_corrupted_crc(speed in 0u16..2000, request_telemetry: bool, flip in 1u16..16) {
+        let frame = Frame::new(speed, request_telemetry).unwrap();
+        prop_assert!(Frame::from_raw(frame.inner() ^ flip).is_err());
+    }
+}